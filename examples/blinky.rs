@@ -6,7 +6,8 @@
 use cortex_m_rt::entry;
 use defmt_rtt as _;
 use pac25_hal as hal;
-use pac25_hal::scc::{ClockSource, Config, PllConfig, PllOutPrescaler, SystemClock};
+use pac25_hal::scc::{ClockSource, Config, PllConfig, SystemClock};
+use pac25_hal::time::Hertz;
 use panic_probe as _;
 
 use hal::pac;
@@ -21,17 +22,12 @@ fn main() -> ! {
     let mut led = gpiod.pd4.into_push_pull_output();
 
     // Set the system clock to 150MHz
+    let (pll, _) = PllConfig::for_target(Hertz::MHz(4), Hertz::MHz(150)).unwrap();
     let _scc = dp.SCC.freeze(
         Config::default()
             .frclk(ClockSource::RefClock)
             .sclk(SystemClock::PLLCLK)
-            .pll(
-                PllConfig::default()
-                    .enabled(true)
-                    .indiv(1)
-                    .fbdiv(75)
-                    .outdiv(PllOutPrescaler::Div2),
-            ),
+            .pll(pll),
     );
 
     loop {
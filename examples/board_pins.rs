@@ -0,0 +1,47 @@
+//! Describes a board with a pin map and blinks its LED while the button is
+//! pressed
+
+#![no_main]
+#![no_std]
+
+use cortex_m_rt::entry;
+use defmt_rtt as _;
+use pac25_hal as hal;
+use panic_probe as _;
+
+use hal::pac;
+use hal::prelude::*;
+
+hal::board_pins! {
+    /// Pins of the board
+    pub struct Board {
+        /// User LED
+        led: PD4 => push_pull_output,
+        /// User button, closes to ground
+        button: PD5 => pull_up_input,
+        /// PWM output of TIMER_C
+        pwm: PE0 => af1,
+    }
+}
+
+#[entry]
+fn main() -> ! {
+    let dp = pac::Peripherals::take().unwrap();
+    let gpiod = dp.GPIOD.split();
+    let gpioe = dp.GPIOE.split();
+
+    let mut board = Board::new(gpiod.pd4, gpiod.pd5, gpioe.pe0);
+    let _pwm = board.pwm;
+
+    loop {
+        if board.button.is_low() {
+            board.led.toggle();
+        } else {
+            board.led.set_low();
+        }
+
+        for _ in 0..100_000 {
+            continue;
+        }
+    }
+}
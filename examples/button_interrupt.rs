@@ -0,0 +1,58 @@
+//! Toggles an LED whenever a button pulls its input pin low
+
+#![no_main]
+#![no_std]
+
+use core::cell::RefCell;
+use cortex_m::interrupt::Mutex;
+use cortex_m_rt::entry;
+use defmt_rtt as _;
+use pac25_hal as hal;
+use panic_probe as _;
+
+use hal::gpio::{Edge, Input, Output, PD4, PD5};
+use hal::pac::{self, interrupt};
+use hal::prelude::*;
+
+static BUTTON: Mutex<RefCell<Option<PD5<Input>>>> = Mutex::new(RefCell::new(None));
+static LED: Mutex<RefCell<Option<PD4<Output>>>> = Mutex::new(RefCell::new(None));
+
+#[entry]
+fn main() -> ! {
+    defmt::println!("Waiting for button presses.");
+
+    let dp = pac::Peripherals::take().unwrap();
+    let gpiod = dp.GPIOD.split();
+    let led = gpiod.pd4.into_push_pull_output();
+    let mut button = gpiod.pd5.into_pull_up_input();
+
+    button.trigger_on_edge(Edge::Falling);
+    button.clear_interrupt_pending_bit();
+    button.enable_interrupt();
+    let irq = button.interrupt();
+
+    cortex_m::interrupt::free(|cs| {
+        BUTTON.borrow(cs).replace(Some(button));
+        LED.borrow(cs).replace(Some(led));
+    });
+
+    unsafe {
+        pac::NVIC::unmask(irq);
+    }
+
+    loop {
+        cortex_m::asm::wfi();
+    }
+}
+
+#[interrupt]
+fn GPIOD() {
+    cortex_m::interrupt::free(|cs| {
+        if let Some(button) = BUTTON.borrow(cs).borrow_mut().as_mut() {
+            button.clear_interrupt_pending_bit();
+        }
+        if let Some(led) = LED.borrow(cs).borrow_mut().as_mut() {
+            led.toggle();
+        }
+    });
+}
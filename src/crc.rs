@@ -45,6 +45,43 @@ impl Crc {
             .modify(|_, w| unsafe { w.value().bits(seed) });
     }
 
+    /// Enable or disable bit reflection of the input data and of the result.
+    ///
+    /// Reflected CRCs, such as the Dallas/Maxim CRC-8, process the data least
+    /// significant bit first.
+    pub fn reflect(&self, input: bool, output: bool) {
+        self.crc
+            .ctl()
+            .modify(|_, w| w.inref().bit(input).outref().bit(output));
+    }
+
+    /// Feed bytes into the CRC engine.
+    ///
+    /// The engine must be configured with [`DataWidth::Width8Bits`].
+    pub fn feed_u8(&self, data: &[u8]) {
+        for &byte in data {
+            self.crc
+                .datain()
+                .write(|w| unsafe { w.value().bits(byte as u32) });
+        }
+    }
+
+    /// Feed words into the CRC engine.
+    ///
+    /// The engine must be configured with [`DataWidth::Width32Bits`].
+    pub fn feed_u32(&self, data: &[u32]) {
+        for &word in data {
+            self.crc.datain().write(|w| unsafe { w.value().bits(word) });
+        }
+    }
+
+    /// Read the CRC result.
+    ///
+    /// In the CRC-8 configuration, the result is held in bits 0:7.
+    pub fn result(&self) -> u16 {
+        self.crc.dataout().read().value().bits()
+    }
+
     /// Return ownership of the peripheral.
     ///
     /// This can be used in cases where the peripheral needs to be reconfigured
@@ -1,7 +1,33 @@
 use crate::hal::digital::PinState;
 use core::fmt;
 use core::marker::PhantomData;
+pub mod alt;
+mod board;
+#[doc(hidden)]
+pub use board::into_alternate as __into_alternate;
 mod convert;
+pub use convert::PinMode;
+mod debounce;
+pub use debounce::{DebounceEvent, Debounced};
+mod dynamic;
+pub use dynamic::{Dynamic, DynamicPin, PinModeError};
+mod erased;
+pub use erased::{EPin, ErasedPin};
+mod exti;
+pub use exti::{Edge, ExtiPin};
+mod group;
+pub use group::{write_port, PinGroup};
+mod hal_1;
+mod partially_erased;
+pub use partially_erased::{PEPin, PartiallyErasedPin};
+mod state;
+pub use state::{PadMode, PadState, PortState};
+#[cfg(feature = "async")]
+mod wait;
+#[cfg(feature = "async")]
+pub use wait::on_interrupt;
+mod wakeup;
+pub use wakeup::{wait_for_wakeup, WakeupPin, WakeupSource};
 
 pub use Input as DefaultMode;
 
@@ -38,13 +64,18 @@ pub trait PinExt {
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct Alternate<const A: u8, Otype = PushPull>(PhantomData<Otype>);
 
+/// Analog mode (type state)
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Analog;
+
 /// Input mode (type state)
 #[derive(Debug, Default)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct Input;
 
 /// Pull setting for an input
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum Pull {
     /// Floating
@@ -73,7 +104,17 @@ pub struct Output<MODE = PushPull> {
 pub struct PushPull;
 
 /// JTAG/SWD mode (type state)
-pub type Debugger = Alternate<0b011, PushPull>;
+///
+/// Not a [`PinMode`], so a pin only leaves it through
+/// `into_gpio_unchecked` or `release_debug`.
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Debugger;
+
+impl Debugger {
+    /// Alternate function of the SWD port
+    pub(crate) const MUXSEL: u32 = 0b011;
+}
 
 pub(crate) mod marker {
     /// Marker trait that show if `ExtiPin` can be implemented
@@ -102,8 +143,18 @@ impl<const A: u8, Otype> marker::OutputSpeed for Alternate<A, Otype> {}
 impl<Otype> marker::Active for Output<Otype> {}
 impl<const A: u8, Otype> marker::Active for Alternate<A, Otype> {}
 impl marker::NotAlt for Input {}
+impl marker::NotAlt for Analog {}
 impl<Otype> marker::NotAlt for Output<Otype> {}
 
+/// Marker trait for analog pins wired to an ADC input channel
+///
+/// Analog drivers can require this trait in their constructors so only a pin
+/// in [`Analog`] mode that is routed to the ADC can be passed in.
+pub trait AdcChannel {
+    /// ADC input channel the pin is wired to
+    const CHANNEL: u8;
+}
+
 /// GPIO Pin drive strength
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -118,6 +169,21 @@ pub enum DriveStrength {
     _25mA = 0b111,
 }
 
+impl DriveStrength {
+    const fn from_bits(bits: u32) -> Self {
+        match bits & 0b111 {
+            0b000 => DriveStrength::_6mA,
+            0b001 => DriveStrength::_8mA,
+            0b010 => DriveStrength::_11mA,
+            0b011 => DriveStrength::_14mA,
+            0b100 => DriveStrength::_17mA,
+            0b101 => DriveStrength::_20mA,
+            0b110 => DriveStrength::_22mA,
+            _ => DriveStrength::_25mA,
+        }
+    }
+}
+
 macro_rules! af {
     ($($i:literal: $AFi:ident),+) => {
         $(
@@ -211,24 +277,13 @@ where
 {
     /// Set the internal pull-up and pull-down resistor
     pub fn set_internal_resistor(&mut self, resistor: Pull) {
-        let offset = N;
-
-        // for safety reasons we need to ensure the pull-up and pull-down are
-        // not activated at the same time.
-
         unsafe {
-            (*Gpio::<P>::scc_puen_ptr()).modify(|r, w| w.bits(r.bits() & !(1 << offset)));
-            (*Gpio::<P>::scc_pden_ptr()).modify(|r, w| w.bits(r.bits() & !(1 << offset)));
-
-            match resistor {
-                Pull::Up => {
-                    (*Gpio::<P>::scc_puen_ptr()).modify(|r, w| w.bits(r.bits() | (1 << offset)));
-                }
-                Pull::Down => {
-                    (*Gpio::<P>::scc_pden_ptr()).modify(|r, w| w.bits(r.bits() | (1 << offset)));
-                }
-                Pull::None => {}
-            }
+            write_pull(
+                Gpio::<P>::scc_puen_ptr(),
+                Gpio::<P>::scc_pden_ptr(),
+                N,
+                resistor,
+            );
         }
     }
 
@@ -257,6 +312,116 @@ where
     }
 }
 
+/// Writes the pull-up and pull-down enables of pin `n` in a port's SCC
+/// PxPUEN and PxPDEN registers.
+unsafe fn write_pull(
+    puen: *const crate::pac::scc::PAPUEN,
+    pden: *const crate::pac::scc::PAPDEN,
+    n: u8,
+    resistor: Pull,
+) {
+    // The registers are shared by all pins of the port, so the update runs
+    // in a critical section. For safety reasons we also need to ensure the
+    // pull-up and pull-down are not activated at the same time.
+    critical_section::with(|_| {
+        (*puen).modify(|r, w| w.bits(r.bits() & !(1 << n)));
+        (*pden).modify(|r, w| w.bits(r.bits() & !(1 << n)));
+
+        match resistor {
+            Pull::Up => {
+                (*puen).modify(|r, w| w.bits(r.bits() | (1 << n)));
+            }
+            Pull::Down => {
+                (*pden).modify(|r, w| w.bits(r.bits() | (1 << n)));
+            }
+            Pull::None => {}
+        }
+    });
+}
+
+/// Reads the pull-up and pull-down enables of pin `n` from a port's SCC
+/// PxPUEN and PxPDEN registers.
+unsafe fn read_pull(
+    puen: *const crate::pac::scc::PAPUEN,
+    pden: *const crate::pac::scc::PAPDEN,
+    n: u8,
+) -> Pull {
+    if (*puen).read().bits() & (1 << n) != 0 {
+        Pull::Up
+    } else if (*pden).read().bits() & (1 << n) != 0 {
+        Pull::Down
+    } else {
+        Pull::None
+    }
+}
+
+impl<const P: char, const N: u8, MODE> Pin<P, N, MODE>
+where
+    MODE: marker::OutputSpeed,
+{
+    /// Set pin drive strength
+    pub fn set_drive_strength(&mut self, strength: DriveStrength) {
+        let offset = 4 * N;
+
+        critical_section::with(|_| unsafe {
+            (*Gpio::<P>::scc_ds_ptr()).modify(|r, w| {
+                w.bits((r.bits() & !(0b111 << offset)) | ((strength as u32) << offset))
+            });
+        });
+    }
+
+    /// Set pin drive strength
+    pub fn drive_strength(mut self, strength: DriveStrength) -> Self {
+        self.set_drive_strength(strength);
+        self
+    }
+
+    /// Get the currently configured pin drive strength
+    pub fn get_drive_strength(&self) -> DriveStrength {
+        let offset = 4 * N;
+
+        // NOTE(unsafe) atomic read with no side effects
+        let bits = unsafe { ((*Gpio::<P>::scc_ds_ptr()).read().bits() >> offset) & 0b111 };
+        DriveStrength::from_bits(bits)
+    }
+}
+
+impl<const P: char, const N: u8, MODE> Pin<P, N, MODE> {
+    /// Erases the pin number from the type
+    ///
+    /// This is useful when you want to collect the pins into an array where you
+    /// need all the elements to have the same type
+    pub fn erase_number(self) -> PartiallyErasedPin<P, MODE> {
+        PartiallyErasedPin::new(N)
+    }
+
+    /// Erases the pin number and the port from the type
+    ///
+    /// This is useful when you want to collect the pins into an array where you
+    /// need all the elements to have the same type
+    pub fn erase(self) -> ErasedPin<MODE> {
+        ErasedPin::new(P as u8 - b'A', N)
+    }
+}
+
+impl<const P: char, const N: u8, MODE> From<Pin<P, N, MODE>> for PartiallyErasedPin<P, MODE> {
+    /// Pin-to-partially erased pin conversion using the [`From`] trait.
+    ///
+    /// Note that [`From`] is the reciprocal of [`Into`].
+    fn from(p: Pin<P, N, MODE>) -> Self {
+        p.erase_number()
+    }
+}
+
+impl<const P: char, const N: u8, MODE> From<Pin<P, N, MODE>> for ErasedPin<MODE> {
+    /// Pin-to-erased pin conversion using the [`From`] trait.
+    ///
+    /// Note that [`From`] is the reciprocal of [`Into`].
+    fn from(p: Pin<P, N, MODE>) -> Self {
+        p.erase()
+    }
+}
+
 impl<const P: char, const N: u8, MODE> Pin<P, N, MODE> {
     /// Set the output of the pin regardless of its mode.
     /// Primarily used to set the output value of the pin
@@ -293,6 +458,12 @@ impl<const P: char, const N: u8, MODE> Pin<P, N, MODE> {
         // NOTE(unsafe) atomic read with no side effects
         unsafe { (*Gpio::<P>::ptr()).in_().read().bits() & (1 << N) == 0 }
     }
+
+    /// Reads back the internal pull-up and pull-down resistor setting
+    #[inline(always)]
+    fn _internal_resistor(&self) -> Pull {
+        unsafe { read_pull(Gpio::<P>::scc_puen_ptr(), Gpio::<P>::scc_pden_ptr(), N) }
+    }
 }
 
 impl<const P: char, const N: u8, MODE> Pin<P, N, Output<MODE>> {
@@ -387,11 +558,20 @@ where
 
 macro_rules! gpio {
     ($GPIOX:ident, $gpiox:ident, $PEPin:ident, $port_id:expr, $PXn:ident, [
-        $($PXi:ident: ($pxi:ident, $i:expr, [$($A:literal),*] $(, $MODE:ty)?),)+
+        $($PXi:ident: ($pxi:ident, $i:expr, [$($A:literal),*] $(, $MODE:ty)? $(, {
+            $($AF:literal: $Signal:path,)*
+        })?),)+
     ]) => {
         /// GPIO
         pub mod $gpiox {
+            use super::alt::*;
             use crate::pac::$GPIOX;
+            // Peripherals named by the signal tables, not every port uses all
+            #[allow(unused_imports)]
+            use crate::pac::{
+                CAN, I2C, SSPA, SSPB, SSPC, SSPD, TIMER_A, TIMER_B, TIMER_C, TIMER_D, USARTA,
+                USARTB, USARTC, USARTD,
+            };
 
             /// GPIO parts
             pub struct Parts {
@@ -413,12 +593,33 @@ macro_rules! gpio {
                 }
             }
 
-            /*
+            impl Parts {
+                /// Takes back every pin of the port, in any mode, returns them to
+                /// their reset configuration and releases the GPIO peripheral.
+                ///
+                /// The mode, alternate function, pull-up and pull-down and drive
+                /// strength of every pin are restored and all pin interrupts are
+                /// disabled.
+                #[allow(clippy::too_many_arguments)]
+                pub fn free($($pxi: $PXi<impl Sized>,)+) -> $GPIOX {
+                    // Owning the pins is all that is needed, whatever their mode
+                    let _ = ($($pxi,)+);
+
+                    // Alternate functions of the pins that are not handed out
+                    // as GPIOs by `split`
+                    const MUXSEL: u32 = 0 $($(| (<$MODE>::MUXSEL << (4 * $i)))?)+;
+
+                    unsafe {
+                        super::Gpio::<$port_id>::reset(MUXSEL);
+                        $GPIOX::steal()
+                    }
+                }
+            }
+
             #[doc="Common type for "]
             #[doc=stringify!($GPIOX)]
             #[doc=" related pins"]
             pub type $PXn<MODE> = super::PartiallyErasedPin<$port_id, MODE>;
-             */
 
             $(
                 #[doc=stringify!($PXi)]
@@ -428,18 +629,50 @@ macro_rules! gpio {
                 $(
                     impl<MODE> super::marker::IntoAf<$A> for $PXi<MODE> { }
                 )*
+
+                $($(
+                    impl<Otype> $Signal for $PXi<super::Alternate<$AF, Otype>> { }
+                )*)?
             )+
 
         }
 
-        pub use $gpiox::{ $($PXi,)+ };
+        pub use $gpiox::{ $PXn, $($PXi,)+ };
     }
 }
-use gpio;
+
+macro_rules! adc_channels {
+    ($($PXi:ident: $channel:literal,)+) => {
+        $(
+            impl AdcChannel for $PXi<Analog> {
+                const CHANNEL: u8 = $channel;
+            }
+        )+
+    };
+}
 
 mod pac25;
 pub use pac25::*;
 
+/// Returns the register block of the port with index `port`, starting from 0 for GPIOA.
+#[inline]
+pub(crate) fn port_block(port: u8) -> &'static crate::pac::gpioa::RegisterBlock {
+    // This function uses pointer arithmetic instead of branching to be more efficient
+
+    // The logic relies on the following assumptions:
+    // - GPIOA register is available on all chips
+    // - all gpio register blocks have the same layout
+    // - consecutive gpio register blocks have the same offset between them, namely 0x0400
+    // - `port` is a valid port index
+    const GPIO_REGISTER_OFFSET: usize = 0x0400;
+
+    let offset = GPIO_REGISTER_OFFSET * port as usize;
+    let block_ptr =
+        (crate::pac::GPIOA::ptr() as usize + offset) as *const crate::pac::gpioa::RegisterBlock;
+
+    unsafe { &*block_ptr }
+}
+
 struct Gpio<const P: char>;
 impl<const P: char> Gpio<P> {
     const fn ptr() -> *const crate::pac::gpioa::RegisterBlock {
@@ -462,6 +695,30 @@ impl<const P: char> Gpio<P> {
         }
     }
 
+    /// Restores the GPIO and SCC pad registers of the port to their reset
+    /// values, with the port multiplexer set to `muxsel`.
+    ///
+    /// # Safety
+    ///
+    /// The caller must own every pin of the port.
+    unsafe fn reset(muxsel: u32) {
+        let block = &*Self::ptr();
+
+        block.inten().reset();
+        block.inttype().reset();
+        block.intvalue().reset();
+        block.intedgeboth().reset();
+        block.intclear().write(|w| w.bits(0xff));
+        block.mode().reset();
+        block.outmask().reset();
+        block.doclear().write(|w| w.bits(0xff));
+
+        (*Self::scc_muxsel_ptr()).write(|w| w.bits(muxsel));
+        (*Self::scc_puen_ptr()).reset();
+        (*Self::scc_pden_ptr()).reset();
+        (*Self::scc_ds_ptr()).reset();
+    }
+
     fn scc_muxsel_ptr() -> *const crate::pac::scc::PAMUXSEL {
         unsafe {
             match P {
@@ -527,4 +784,26 @@ impl<const P: char> Gpio<P> {
             }
         }
     }
+
+    fn scc_ds_ptr() -> *const crate::pac::scc::PADS {
+        unsafe {
+            match P {
+                #[cfg(feature = "gpioa")]
+                'A' => (*crate::pac::SCC::ptr()).pads().as_ptr() as *const _,
+                #[cfg(feature = "gpiob")]
+                'B' => (*crate::pac::SCC::ptr()).pbds().as_ptr() as *const _,
+                #[cfg(feature = "gpioc")]
+                'C' => (*crate::pac::SCC::ptr()).pcds().as_ptr() as *const _,
+                #[cfg(feature = "gpiod")]
+                'D' => (*crate::pac::SCC::ptr()).pdds().as_ptr() as *const _,
+                #[cfg(feature = "gpioe")]
+                'E' => (*crate::pac::SCC::ptr()).peds().as_ptr() as *const _,
+                #[cfg(feature = "gpiof")]
+                'F' => (*crate::pac::SCC::ptr()).pfds().as_ptr() as *const _,
+                #[cfg(feature = "gpiog")]
+                'G' => (*crate::pac::SCC::ptr()).pgds().as_ptr() as *const _,
+                _ => panic!("Unknown GPIO port"),
+            }
+        }
+    }
 }
@@ -0,0 +1,39 @@
+//! Peripheral signal marker traits
+//!
+//! The traits are implemented for the pins in the alternate function mode
+//! that routes the matching peripheral signal to the pad. Drivers take their
+//! pins through these traits so a pin that is not muxed to the right signal
+//! fails to build.
+
+/// SPI clock pin of SSP instance `SSP`
+pub trait PinSck<SSP> {}
+
+/// SPI master out slave in pin of SSP instance `SSP`
+pub trait PinMosi<SSP> {}
+
+/// SPI master in slave out pin of SSP instance `SSP`
+pub trait PinMiso<SSP> {}
+
+/// SPI slave select pin of SSP instance `SSP`
+pub trait PinSs<SSP> {}
+
+/// Transmit pin of USART instance `USART`
+pub trait PinTx<USART> {}
+
+/// Receive pin of USART instance `USART`
+pub trait PinRx<USART> {}
+
+/// Clock pin of I2C instance `I2C`
+pub trait PinScl<I2C> {}
+
+/// Data pin of I2C instance `I2C`
+pub trait PinSda<I2C> {}
+
+/// Transmit pin of CAN instance `CAN`
+pub trait PinCanTx<CAN> {}
+
+/// Receive pin of CAN instance `CAN`
+pub trait PinCanRx<CAN> {}
+
+/// PWM output pin of channel `C` of timer `TIM`
+pub trait PinPwm<TIM, const C: u8> {}
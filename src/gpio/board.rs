@@ -0,0 +1,123 @@
+//! Board pin maps for board support crates
+
+use super::*;
+
+/// Declares a board structure holding named, configured pins.
+///
+/// Every entry names a field, the pin it is wired to and the mode the pin is
+/// put in. The generated `new` function takes the pins from the `Parts` of
+/// one or more `split()` calls, in declaration order and in any mode, and
+/// converts them.
+///
+/// Supported modes are `input`, `floating_input`, `pull_up_input`,
+/// `pull_down_input`, `push_pull_output`, `open_drain_output`, `analog`,
+/// `af0` to `af7` and `af0_open_drain` to `af7_open_drain`. Alternate
+/// functions are checked against the pin's alternate function table.
+///
+/// Using the same pin twice is a compile time error.
+///
+/// ```ignore
+/// pac25_hal::board_pins! {
+///     /// Pins of the evaluation board
+///     pub struct Board {
+///         /// Green LED
+///         led: PD4 => push_pull_output,
+///         button: PD5 => pull_up_input,
+///         pwm: PE0 => af1,
+///     }
+/// }
+///
+/// let gpiod = dp.GPIOD.split();
+/// let gpioe = dp.GPIOE.split();
+/// let board = Board::new(gpiod.pd4, gpiod.pd5, gpioe.pe0);
+/// ```
+#[macro_export]
+macro_rules! board_pins {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $Board:ident {
+            $(
+                $(#[$field_attr:meta])*
+                $field:ident: $PIN:ident => $mode:ident
+            ),+ $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        $vis struct $Board {
+            $(
+                $(#[$field_attr])*
+                pub $field: $crate::gpio::$PIN<$crate::board_pins!(@mode $mode)>,
+            )+
+        }
+
+        impl $Board {
+            /// Configures the board pins.
+            #[allow(clippy::too_many_arguments)]
+            pub fn new($($field: $crate::gpio::$PIN<impl $crate::gpio::PinMode>),+) -> Self {
+                Self {
+                    $(
+                        $field: $crate::board_pins!(@into $field, $mode),
+                    )+
+                }
+            }
+        }
+
+        // A pin used twice defines the same variant twice
+        const _: () = {
+            #[allow(dead_code)]
+            enum PinUsage {
+                $($PIN,)+
+            }
+        };
+    };
+
+    (@mode input) => { $crate::gpio::Input };
+    (@mode floating_input) => { $crate::gpio::Input };
+    (@mode pull_up_input) => { $crate::gpio::Input };
+    (@mode pull_down_input) => { $crate::gpio::Input };
+    (@mode push_pull_output) => { $crate::gpio::Output<$crate::gpio::PushPull> };
+    (@mode open_drain_output) => { $crate::gpio::Output<$crate::gpio::OpenDrain> };
+    (@mode analog) => { $crate::gpio::Analog };
+    (@mode af0) => { $crate::gpio::AF0 };
+    (@mode af1) => { $crate::gpio::AF1 };
+    (@mode af2) => { $crate::gpio::AF2 };
+    (@mode af3) => { $crate::gpio::AF3 };
+    (@mode af4) => { $crate::gpio::AF4 };
+    (@mode af5) => { $crate::gpio::AF5 };
+    (@mode af6) => { $crate::gpio::AF6 };
+    (@mode af7) => { $crate::gpio::AF7 };
+    (@mode af0_open_drain) => { $crate::gpio::AF0<$crate::gpio::OpenDrain> };
+    (@mode af1_open_drain) => { $crate::gpio::AF1<$crate::gpio::OpenDrain> };
+    (@mode af2_open_drain) => { $crate::gpio::AF2<$crate::gpio::OpenDrain> };
+    (@mode af3_open_drain) => { $crate::gpio::AF3<$crate::gpio::OpenDrain> };
+    (@mode af4_open_drain) => { $crate::gpio::AF4<$crate::gpio::OpenDrain> };
+    (@mode af5_open_drain) => { $crate::gpio::AF5<$crate::gpio::OpenDrain> };
+    (@mode af6_open_drain) => { $crate::gpio::AF6<$crate::gpio::OpenDrain> };
+    (@mode af7_open_drain) => { $crate::gpio::AF7<$crate::gpio::OpenDrain> };
+    (@mode $mode:ident) => {
+        compile_error!(concat!("Unknown pin mode `", stringify!($mode), "`"))
+    };
+
+    (@into $pin:ident, input) => { $pin.into_input() };
+    (@into $pin:ident, floating_input) => { $pin.into_floating_input() };
+    (@into $pin:ident, pull_up_input) => { $pin.into_pull_up_input() };
+    (@into $pin:ident, pull_down_input) => { $pin.into_pull_down_input() };
+    (@into $pin:ident, push_pull_output) => { $pin.into_push_pull_output() };
+    (@into $pin:ident, open_drain_output) => { $pin.into_open_drain_output() };
+    (@into $pin:ident, analog) => { $pin.into_analog() };
+    (@into $pin:ident, $af:ident) => { $crate::gpio::__into_alternate($pin) };
+}
+
+/// Converts `pin` to the alternate function mode inferred from the caller,
+/// checking the alternate function table of the pin.
+#[doc(hidden)]
+pub fn into_alternate<const P: char, const N: u8, MODE, const A: u8, Otype>(
+    pin: Pin<P, N, MODE>,
+) -> Pin<P, N, Alternate<A, Otype>>
+where
+    MODE: PinMode,
+    Alternate<A, Otype>: PinMode,
+    Pin<P, N, MODE>: marker::IntoAf<A>,
+{
+    pin.into_mode()
+}
@@ -1,6 +1,20 @@
 use super::*;
 
 impl<const P: char, const N: u8, MODE: PinMode> Pin<P, N, MODE> {
+    /// Configures the pin to operate as an analog input pin, disabling the
+    /// digital input buffer and the internal pull-up and pull-down resistors
+    pub fn into_analog(self) -> Pin<P, N, Analog> {
+        unsafe {
+            write_pull(
+                Gpio::<P>::scc_puen_ptr(),
+                Gpio::<P>::scc_pden_ptr(),
+                N,
+                Pull::None,
+            );
+        }
+        self.into_mode()
+    }
+
     /// Configures the pin to operate as a input pin
     pub fn into_input(self) -> Pin<P, N, Input> {
         self.into_mode()
@@ -54,6 +68,30 @@ impl<const P: char, const N: u8, MODE: PinMode> Pin<P, N, MODE> {
         self.into_mode()
     }
 
+    /// Configures the pin to operate in alternate function `A`
+    pub fn into_alternate<const A: u8>(self) -> Pin<P, N, Alternate<A, PushPull>>
+    where
+        Self: marker::IntoAf<A>,
+    {
+        self.into_mode()
+    }
+
+    /// Configures the pin to operate in alternate function `A` with an open drain output
+    pub fn into_alternate_open_drain<const A: u8>(self) -> Pin<P, N, Alternate<A, OpenDrain>>
+    where
+        Self: marker::IntoAf<A>,
+    {
+        self.into_mode()
+    }
+
+    /// Configures the pin as a pin that can change between input
+    /// and output without changing the type. It starts out
+    /// as a floating input
+    pub fn into_dynamic(self) -> DynamicPin<P, N> {
+        self.into_floating_input();
+        DynamicPin::new(Dynamic::InputFloating)
+    }
+
     /// Puts `self` into mode `M`.
     ///
     /// This violates the type state constraints from `MODE`, so callers must
@@ -71,16 +109,181 @@ impl<const P: char, const N: u8, MODE: PinMode> Pin<P, N, MODE> {
     }
 }
 
+impl<const P: char, const N: u8, const A: u8> Pin<P, N, Alternate<A, PushPull>> {
+    /// Turns pin alternate configuration pin into open drain
+    pub fn set_open_drain(self) -> Pin<P, N, Alternate<A, OpenDrain>> {
+        self.into_mode()
+    }
+}
+
+impl<const P: char, const N: u8> Pin<P, N, Debugger> {
+    /// Takes the pin away from the SWD debug port and configures it as an
+    /// input pin, without checking whether a debugger is attached.
+    ///
+    /// # Lock-out risk
+    ///
+    /// Once both SWD pins are reclaimed the debug port stops working until
+    /// the next reset. If this happens early after every reset, a debugger
+    /// can no longer attach to reflash the device; it then has to be
+    /// connected while holding the device in reset. Prefer
+    /// [`release_debug`](Self::release_debug) or delay the reclaim long
+    /// enough to attach a probe.
+    pub fn into_gpio_unchecked(self) -> Pin<P, N, Input> {
+        Pin::<P, N, Alternate<{ Debugger::MUXSEL as u8 }, PushPull>>::new().into_mode()
+    }
+
+    /// Takes the pin away from the SWD debug port and configures it as an
+    /// input pin, unless a debugger is attached.
+    ///
+    /// Returns the pin unchanged in `Err` if a debugger is attached. See
+    /// [`into_gpio_unchecked`](Self::into_gpio_unchecked) for the lock-out
+    /// risk of reclaiming the debug pins.
+    pub fn release_debug(self) -> Result<Pin<P, N, Input>, Self> {
+        if crate::pac::DCB::is_debugger_attached() {
+            Err(self)
+        } else {
+            Ok(self.into_gpio_unchecked())
+        }
+    }
+}
+
+impl<const P: char, const N: u8, MODE> Pin<P, N, MODE>
+where
+    MODE: PinMode + marker::NotAlt,
+{
+    fn with_mode<M, F, R>(&mut self, f: F) -> R
+    where
+        M: PinMode,
+        F: FnOnce(&mut Pin<P, N, M>) -> R,
+    {
+        self.mode::<M>(); // change physical mode, without changing typestate
+
+        // This will reset the pin back to the original mode when dropped.
+        // (so either when `with_mode` returns or when `f` unwinds)
+        let mut resetti = ResetMode::<P, N, M, MODE>::new();
+
+        f(&mut resetti.pin)
+    }
+
+    /// Temporarily configures this pin as a input.
+    ///
+    /// The closure `f` is called with the reconfigured pin. After it returns,
+    /// the pin will be configured back.
+    pub fn with_input<R>(&mut self, f: impl FnOnce(&mut Pin<P, N, Input>) -> R) -> R {
+        self.with_mode(f)
+    }
+
+    /// Temporarily configures this pin as a pulled up input.
+    ///
+    /// The closure `f` is called with the reconfigured pin. After it returns,
+    /// the pin will be configured back, including its previous pull-up and
+    /// pull-down setting.
+    pub fn with_pull_up_input<R>(&mut self, f: impl FnOnce(&mut Pin<P, N, Input>) -> R) -> R {
+        let resistor = self._internal_resistor();
+        self.with_mode(|pin: &mut Pin<P, N, Input>| {
+            pin.set_internal_resistor(Pull::Up);
+            let r = f(pin);
+            pin.set_internal_resistor(resistor);
+            r
+        })
+    }
+
+    /// Temporarily configures this pin as an open drain output.
+    ///
+    /// The closure `f` is called with the reconfigured pin. After it returns,
+    /// the pin will be configured back.
+    /// The value of the pin after conversion is undefined. If you
+    /// want to control it, use `with_open_drain_output_in_state`
+    pub fn with_open_drain_output<R>(
+        &mut self,
+        f: impl FnOnce(&mut Pin<P, N, Output<OpenDrain>>) -> R,
+    ) -> R {
+        self.with_mode(f)
+    }
+
+    /// Temporarily configures this pin as an open drain output .
+    ///
+    /// The closure `f` is called with the reconfigured pin. After it returns,
+    /// the pin will be configured back.
+    /// Note that the new state is set slightly before conversion
+    /// happens. This can cause a short output glitch if switching
+    /// between output modes
+    pub fn with_open_drain_output_in_state<R>(
+        &mut self,
+        state: PinState,
+        f: impl FnOnce(&mut Pin<P, N, Output<OpenDrain>>) -> R,
+    ) -> R {
+        self._set_state(state);
+        self.with_mode(f)
+    }
+
+    /// Temporarily configures this pin as a push-pull output.
+    ///
+    /// The closure `f` is called with the reconfigured pin. After it returns,
+    /// the pin will be configured back.
+    /// The value of the pin after conversion is undefined. If you
+    /// want to control it, use `with_push_pull_output_in_state`
+    pub fn with_push_pull_output<R>(
+        &mut self,
+        f: impl FnOnce(&mut Pin<P, N, Output<PushPull>>) -> R,
+    ) -> R {
+        self.with_mode(f)
+    }
+
+    /// Temporarily configures this pin as a push-pull output.
+    ///
+    /// The closure `f` is called with the reconfigured pin. After it returns,
+    /// the pin will be configured back.
+    /// Note that the new state is set slightly before conversion
+    /// happens. This can cause a short output glitch if switching
+    /// between output modes
+    pub fn with_push_pull_output_in_state<R>(
+        &mut self,
+        state: PinState,
+        f: impl FnOnce(&mut Pin<P, N, Output<PushPull>>) -> R,
+    ) -> R {
+        self._set_state(state);
+        self.with_mode(f)
+    }
+}
+
+/// Wrapper around a pin that transitions the pin to mode ORIG when dropped
+struct ResetMode<const P: char, const N: u8, CURRENT: PinMode, ORIG: PinMode> {
+    pub pin: Pin<P, N, CURRENT>,
+    _mode: PhantomData<ORIG>,
+}
+impl<const P: char, const N: u8, CURRENT: PinMode, ORIG: PinMode> ResetMode<P, N, CURRENT, ORIG> {
+    fn new() -> Self {
+        Self {
+            pin: Pin::new(),
+            _mode: PhantomData,
+        }
+    }
+}
+impl<const P: char, const N: u8, CURRENT: PinMode, ORIG: PinMode> Drop
+    for ResetMode<P, N, CURRENT, ORIG>
+{
+    fn drop(&mut self) {
+        self.pin.mode::<ORIG>();
+    }
+}
+
 macro_rules! change_mode {
     ($block:expr, $N:ident) => {
         let offset = 2 * $N;
-        unsafe {
+        // MUXSEL and MODE are shared by all pins of the port
+        critical_section::with(|_| unsafe {
+            if MODE::MUXSEL != M::MUXSEL {
+                let offset = 4 * $N;
+                (*Gpio::<P>::scc_muxsel_ptr())
+                    .modify(|r, w| w.bits((r.bits() & !(0b111 << offset)) | (M::MUXSEL << offset)));
+            }
             if MODE::MODE != M::MODE {
                 $block
                     .mode()
                     .modify(|r, w| w.bits((r.bits() & !(0b11 << offset)) | (M::MODE << offset)));
             }
-        }
+        });
     };
 }
 use change_mode;
@@ -94,6 +297,13 @@ pub trait PinMode: crate::Sealed {
 
     #[doc(hidden)]
     const MODE: u32 = u32::MAX;
+    #[doc(hidden)]
+    const MUXSEL: u32 = 0;
+}
+
+impl crate::Sealed for Analog {}
+impl PinMode for Analog {
+    const MODE: u32 = 0b00;
 }
 
 impl crate::Sealed for Input {}
@@ -109,3 +319,14 @@ impl PinMode for Output<OpenDrain> {
 impl PinMode for Output<PushPull> {
     const MODE: u32 = 0b01;
 }
+
+impl<const A: u8, Otype> crate::Sealed for Alternate<A, Otype> {}
+impl<const A: u8> PinMode for Alternate<A, OpenDrain> {
+    const MODE: u32 = 0b10;
+    const MUXSEL: u32 = A as u32;
+}
+
+impl<const A: u8> PinMode for Alternate<A, PushPull> {
+    const MODE: u32 = 0b01;
+    const MUXSEL: u32 = A as u32;
+}
@@ -0,0 +1,190 @@
+//! Software debouncing of input pins
+
+use super::*;
+
+/// Debounced input event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum DebounceEvent {
+    /// The input settled at its active level
+    Pressed,
+    /// The input settled at its inactive level
+    Released,
+}
+
+/// Software debounced input pin
+///
+/// The wrapped pin is sampled on every call to [`update`](Self::update),
+/// typically from a periodic timer tick. A level change is only accepted once
+/// the pin reads the new level for `samples` consecutive updates.
+#[derive(Debug)]
+pub struct Debounced<PIN> {
+    pin: PIN,
+    samples: u8,
+    count: u8,
+    high: bool,
+    active_high: bool,
+}
+
+impl<PIN: ReadPin> Debounced<PIN> {
+    /// Wraps `pin`, accepting a level change after `samples` stable samples.
+    ///
+    /// `active_level` is the level of a pressed button or a closed switch.
+    /// The debounced level starts at the current level of the pin.
+    pub fn new(pin: PIN, samples: u8, active_level: PinState) -> Self {
+        let high = pin.is_high();
+        Self {
+            pin,
+            samples: samples.max(1),
+            count: 0,
+            high,
+            active_high: active_level == PinState::High,
+        }
+    }
+
+    /// Samples the pin and returns the event if the debounced level changed.
+    pub fn update(&mut self) -> Option<DebounceEvent> {
+        if self.pin.is_high() == self.high {
+            self.count = 0;
+            return None;
+        }
+
+        self.count += 1;
+        if self.count < self.samples {
+            return None;
+        }
+
+        self.count = 0;
+        self.high = !self.high;
+        if self.is_pressed() {
+            Some(DebounceEvent::Pressed)
+        } else {
+            Some(DebounceEvent::Released)
+        }
+    }
+
+    /// Is the debounced input at its active level?
+    #[inline(always)]
+    pub fn is_pressed(&self) -> bool {
+        self.high == self.active_high
+    }
+
+    /// Debounced level of the input
+    #[inline(always)]
+    pub fn level(&self) -> PinState {
+        if self.high {
+            PinState::High
+        } else {
+            PinState::Low
+        }
+    }
+
+    /// Releases the wrapped pin.
+    pub fn release(self) -> PIN {
+        self.pin
+    }
+}
+
+impl<PIN: ReadPin> ReadPin for Debounced<PIN> {
+    #[inline(always)]
+    fn is_low(&self) -> bool {
+        !self.high
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::Cell;
+
+    struct MockPin<'a>(&'a Cell<bool>);
+
+    impl ReadPin for MockPin<'_> {
+        fn is_low(&self) -> bool {
+            !self.0.get()
+        }
+    }
+
+    /// Feeds `script` to the debouncer, one sample per update, and returns
+    /// what each update reported, `None` where the level did not change.
+    fn run<const N: usize>(
+        debounced: &mut Debounced<MockPin>,
+        level: &Cell<bool>,
+        script: [u8; N],
+    ) -> [Option<DebounceEvent>; N] {
+        script.map(|sample| {
+            level.set(sample != 0);
+            debounced.update()
+        })
+    }
+
+    #[test]
+    fn bouncing_press_and_release_active_low() {
+        let level = Cell::new(true);
+        let mut button = Debounced::new(MockPin(&level), 3, PinState::Low);
+        assert!(!button.is_pressed());
+
+        let events = run(&mut button, &level, [0, 1, 0, 0, 1, 0, 0, 0, 0, 0]);
+        let pressed = events.iter().position(|e| e.is_some());
+        assert_eq!(pressed, Some(7));
+        assert_eq!(events[7], Some(DebounceEvent::Pressed));
+        assert_eq!(events.iter().filter(|e| e.is_some()).count(), 1);
+        assert!(button.is_pressed());
+        assert_eq!(button.level(), PinState::Low);
+
+        let events = run(&mut button, &level, [1, 0, 1, 1, 0, 1, 1, 1, 1]);
+        assert_eq!(events.iter().position(|e| e.is_some()), Some(7));
+        assert_eq!(events[7], Some(DebounceEvent::Released));
+        assert!(!button.is_pressed());
+        assert_eq!(button.level(), PinState::High);
+    }
+
+    #[test]
+    fn active_high_switch() {
+        let level = Cell::new(false);
+        let mut switch = Debounced::new(MockPin(&level), 2, PinState::High);
+
+        let events = run(&mut switch, &level, [1, 0, 1, 1, 1]);
+        assert_eq!(
+            events,
+            [None, None, None, Some(DebounceEvent::Pressed), None]
+        );
+        assert!(switch.is_high());
+
+        let events = run(&mut switch, &level, [0, 0]);
+        assert_eq!(events, [None, Some(DebounceEvent::Released)]);
+        assert!(switch.is_low());
+    }
+
+    #[test]
+    fn glitches_shorter_than_sample_count_are_ignored() {
+        let level = Cell::new(true);
+        let mut button = Debounced::new(MockPin(&level), 4, PinState::Low);
+
+        let events = run(&mut button, &level, [0, 0, 0, 1, 0, 0, 0, 1, 1, 1]);
+        assert!(events.iter().all(|e| e.is_none()));
+        assert!(!button.is_pressed());
+    }
+
+    #[test]
+    fn initial_level_does_not_report_event() {
+        let level = Cell::new(false);
+        let mut button = Debounced::new(MockPin(&level), 1, PinState::Low);
+        assert!(button.is_pressed());
+
+        let events = run(&mut button, &level, [0, 0, 0]);
+        assert_eq!(events, [None, None, None]);
+    }
+
+    #[test]
+    fn zero_samples_behaves_as_one() {
+        let level = Cell::new(true);
+        let mut button = Debounced::new(MockPin(&level), 0, PinState::Low);
+
+        let events = run(&mut button, &level, [0, 1]);
+        assert_eq!(
+            events,
+            [Some(DebounceEvent::Pressed), Some(DebounceEvent::Released)]
+        );
+    }
+}
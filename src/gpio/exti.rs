@@ -0,0 +1,155 @@
+use super::*;
+use crate::pac::Interrupt;
+
+/// Edge that triggers a GPIO interrupt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Edge {
+    /// Rising edge
+    Rising,
+    /// Falling edge
+    Falling,
+    /// Both rising and falling edges
+    RisingFalling,
+}
+
+/// External interrupt configuration for GPIO pins
+///
+/// Each GPIO port has a single interrupt line shared by all of its pins. After
+/// the trigger is configured and the pin interrupt enabled, the port interrupt
+/// returned by [`ExtiPin::interrupt`] must be unmasked in the NVIC.
+pub trait ExtiPin {
+    /// Trigger the interrupt on the given edge
+    fn trigger_on_edge(&mut self, edge: Edge);
+
+    /// Trigger the interrupt for as long as the pin is at the given level
+    fn trigger_on_level(&mut self, level: PinState);
+
+    /// Enable the pin interrupt
+    fn enable_interrupt(&mut self);
+
+    /// Disable (mask) the pin interrupt
+    fn disable_interrupt(&mut self);
+
+    /// Clear the interrupt pending flag for this pin
+    fn clear_interrupt_pending_bit(&mut self);
+
+    /// Reads the interrupt pending flag for this pin, taking the interrupt mask into account
+    fn check_interrupt(&self) -> bool;
+
+    /// Reads the interrupt pending flag for this pin, regardless of whether the interrupt is enabled
+    fn check_interrupt_raw(&self) -> bool;
+
+    /// The NVIC interrupt shared by all pins of this pin's port
+    fn interrupt(&self) -> Interrupt;
+}
+
+impl<PIN> ExtiPin for PIN
+where
+    PIN: PinExt,
+    PIN::Mode: marker::Interruptible,
+{
+    #[inline(always)]
+    fn trigger_on_edge(&mut self, edge: Edge) {
+        let block = port_block(self.port_id());
+        let mask = 1 << self.pin_id();
+
+        critical_section::with(|_| unsafe {
+            block.inttype().modify(|r, w| w.bits(r.bits() & !mask));
+            match edge {
+                Edge::Rising => {
+                    block.intvalue().modify(|r, w| w.bits(r.bits() | mask));
+                    block.intedgeboth().modify(|r, w| w.bits(r.bits() & !mask));
+                }
+                Edge::Falling => {
+                    block.intvalue().modify(|r, w| w.bits(r.bits() & !mask));
+                    block.intedgeboth().modify(|r, w| w.bits(r.bits() & !mask));
+                }
+                Edge::RisingFalling => {
+                    block.intedgeboth().modify(|r, w| w.bits(r.bits() | mask));
+                }
+            }
+        });
+    }
+
+    #[inline(always)]
+    fn trigger_on_level(&mut self, level: PinState) {
+        let block = port_block(self.port_id());
+        let mask = 1 << self.pin_id();
+
+        critical_section::with(|_| unsafe {
+            block.intedgeboth().modify(|r, w| w.bits(r.bits() & !mask));
+            match level {
+                PinState::High => block.intvalue().modify(|r, w| w.bits(r.bits() | mask)),
+                PinState::Low => block.intvalue().modify(|r, w| w.bits(r.bits() & !mask)),
+            };
+            block.inttype().modify(|r, w| w.bits(r.bits() | mask));
+        });
+    }
+
+    #[inline(always)]
+    fn enable_interrupt(&mut self) {
+        let block = port_block(self.port_id());
+        critical_section::with(|_| unsafe {
+            block
+                .inten()
+                .modify(|r, w| w.bits(r.bits() | (1 << self.pin_id())));
+        });
+    }
+
+    #[inline(always)]
+    fn disable_interrupt(&mut self) {
+        let block = port_block(self.port_id());
+        critical_section::with(|_| unsafe {
+            block
+                .inten()
+                .modify(|r, w| w.bits(r.bits() & !(1 << self.pin_id())));
+        });
+    }
+
+    #[inline(always)]
+    fn clear_interrupt_pending_bit(&mut self) {
+        // NOTE(unsafe) atomic write to a stateless register
+        unsafe {
+            port_block(self.port_id())
+                .intclear()
+                .write(|w| w.bits(1 << self.pin_id()));
+        }
+    }
+
+    #[inline(always)]
+    fn check_interrupt(&self) -> bool {
+        port_block(self.port_id()).intflagmasked().read().bits() & (1 << self.pin_id()) != 0
+    }
+
+    #[inline(always)]
+    fn check_interrupt_raw(&self) -> bool {
+        port_block(self.port_id()).intflagraw().read().bits() & (1 << self.pin_id()) != 0
+    }
+
+    #[inline(always)]
+    fn interrupt(&self) -> Interrupt {
+        port_interrupt(self.port_id())
+    }
+}
+
+/// Returns the interrupt shared by the pins of the port with index `port`.
+pub(crate) fn port_interrupt(port: u8) -> Interrupt {
+    match port {
+        #[cfg(feature = "gpioa")]
+        0 => Interrupt::GPIOA,
+        #[cfg(feature = "gpiob")]
+        1 => Interrupt::GPIOB,
+        #[cfg(feature = "gpioc")]
+        2 => Interrupt::GPIOC,
+        #[cfg(feature = "gpiod")]
+        3 => Interrupt::GPIOD,
+        #[cfg(feature = "gpioe")]
+        4 => Interrupt::GPIOE,
+        #[cfg(feature = "gpiof")]
+        5 => Interrupt::GPIOF,
+        #[cfg(feature = "gpiog")]
+        6 => Interrupt::GPIOG,
+        _ => panic!("Unknown GPIO port"),
+    }
+}
@@ -0,0 +1,107 @@
+use super::*;
+
+/// Drives the pins of port `P` selected by `mask` to the matching bits of `value`.
+///
+/// The other pins are masked through OUTMASK, so all the selected pins change
+/// state with a single OUT write. The update runs in a critical section and
+/// restores OUTMASK afterwards.
+///
+/// # Safety
+///
+/// The caller must own every pin selected by `mask` and have it configured
+/// as an output.
+#[inline(always)]
+pub unsafe fn write_port<const P: char>(mask: u8, value: u8) {
+    let block = &*Gpio::<P>::ptr();
+
+    critical_section::with(|_| {
+        let outmask = block.outmask().read().bits();
+        block.outmask().write(|w| w.bits(!(mask as u32) & 0xff));
+        block.out().write(|w| w.bits((value & mask) as u32));
+        block.outmask().write(|w| w.bits(outmask));
+    });
+}
+
+/// Group of pins of port `P` that are read and written as a single word.
+///
+/// Bit `i` of the word maps to the `i`-th pin of the group, so the pins do not
+/// need to be consecutive or in order. Because all pins are partially erased
+/// pins of the same port, mixing ports is rejected at compile time.
+pub struct PinGroup<const P: char, const SIZE: usize, MODE> {
+    pins: [PartiallyErasedPin<P, MODE>; SIZE],
+}
+
+impl<const P: char, const SIZE: usize, MODE> PinGroup<P, SIZE, MODE> {
+    /// Creates a group from pins of one port, least significant bit first.
+    pub fn new(pins: [PartiallyErasedPin<P, MODE>; SIZE]) -> Self {
+        Self { pins }
+    }
+
+    /// Releases the pins of the group.
+    pub fn release(self) -> [PartiallyErasedPin<P, MODE>; SIZE] {
+        self.pins
+    }
+
+    /// Mask of the port bits covered by the group
+    #[inline(always)]
+    fn mask(&self) -> u8 {
+        self.pins
+            .iter()
+            .fold(0, |mask, pin| mask | (1 << pin.pin_id()))
+    }
+
+    /// Spreads a group word over the port bits of its pins
+    #[inline(always)]
+    fn spread(&self, value: u8) -> u8 {
+        self.pins
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| value & (1 << i) != 0)
+            .fold(0, |bits, (_, pin)| bits | (1 << pin.pin_id()))
+    }
+
+    /// Gathers the port bits of its pins into a group word
+    #[inline(always)]
+    fn gather(&self, bits: u8) -> u8 {
+        self.pins
+            .iter()
+            .enumerate()
+            .filter(|(_, pin)| bits & (1 << pin.pin_id()) != 0)
+            .fold(0, |value, (i, _)| value | (1 << i))
+    }
+}
+
+impl<const P: char, const SIZE: usize, MODE> From<[PartiallyErasedPin<P, MODE>; SIZE]>
+    for PinGroup<P, SIZE, MODE>
+{
+    fn from(pins: [PartiallyErasedPin<P, MODE>; SIZE]) -> Self {
+        Self::new(pins)
+    }
+}
+
+impl<const P: char, const SIZE: usize, MODE> PinGroup<P, SIZE, Output<MODE>> {
+    /// Drives all pins of the group at once.
+    pub fn write(&mut self, value: u8) {
+        // NOTE(unsafe) the group owns all the pins in the mask and they are outputs
+        unsafe { write_port::<P>(self.mask(), self.spread(value)) }
+    }
+
+    /// Reads back the value the group is driving.
+    pub fn get_state(&self) -> u8 {
+        // NOTE(unsafe) atomic read with no side effects
+        let bits = unsafe { (*Gpio::<P>::ptr()).out().read().bits() as u8 };
+        self.gather(bits)
+    }
+}
+
+impl<const P: char, const SIZE: usize, MODE> PinGroup<P, SIZE, MODE>
+where
+    MODE: marker::Readable,
+{
+    /// Reads the input level of all pins of the group at once.
+    pub fn read(&self) -> u8 {
+        // NOTE(unsafe) atomic read with no side effects
+        let bits = unsafe { (*Gpio::<P>::ptr()).in_().read().bits() as u8 };
+        self.gather(bits)
+    }
+}
@@ -7,64 +7,242 @@ gpio!(GPIOA, gpioa, PA, 'A', PAn, [
     PA0: (pa0, 0, [0]),
     PA1: (pa1, 1, [0, 1]),
     PA2: (pa2, 2, [0, 1]),
-    PA3: (pa3, 3, [0, 1, 2]),
-    PA4: (pa4, 4, [0, 1, 2]),
-    PA5: (pa5, 5, [0, 1, 2]),
-    PA6: (pa6, 6, [0, 1, 2]),
+    PA3: (pa3, 3, [0, 1, 2], {
+        1: PinSck<SSPA>,
+        2: PinSck<SSPB>,
+    }),
+    PA4: (pa4, 4, [0, 1, 2], {
+        1: PinMosi<SSPA>,
+        1: PinTx<USARTA>,
+        2: PinMosi<SSPB>,
+        2: PinTx<USARTB>,
+    }),
+    PA5: (pa5, 5, [0, 1, 2], {
+        1: PinMiso<SSPA>,
+        1: PinRx<USARTA>,
+        2: PinMiso<SSPB>,
+        2: PinRx<USARTB>,
+    }),
+    PA6: (pa6, 6, [0, 1, 2], {
+        1: PinSs<SSPA>,
+        2: PinSs<SSPB>,
+    }),
     PA7: (pa7, 7, [0]),
 ]);
 
 #[cfg(feature = "pac25140")]
 gpio!(GPIOB, gpiob, PB, 'B', PBn, [
-    PB0: (pb0, 0, [0, 1, 2]),
-    PB1: (pb1, 1, [0, 1, 2]),
-    PB2: (pb2, 2, [0, 1, 2]),
-    PB3: (pb3, 3, [0, 1, 2]),
-    PB4: (pb4, 4, [0, 1, 2]),
-    PB5: (pb5, 5, [0, 1, 2]),
-    PB6: (pb6, 6, [0, 1, 2]),
+    PB0: (pb0, 0, [0, 1, 2], {
+        1: PinPwm<TIMER_A, 0>,
+        2: PinPwm<TIMER_B, 0>,
+    }),
+    PB1: (pb1, 1, [0, 1, 2], {
+        1: PinPwm<TIMER_A, 1>,
+        2: PinPwm<TIMER_B, 1>,
+    }),
+    PB2: (pb2, 2, [0, 1, 2], {
+        1: PinPwm<TIMER_A, 2>,
+        2: PinPwm<TIMER_B, 2>,
+    }),
+    PB3: (pb3, 3, [0, 1, 2], {
+        1: PinPwm<TIMER_A, 3>,
+        2: PinPwm<TIMER_B, 3>,
+    }),
+    PB4: (pb4, 4, [0, 1, 2], {
+        1: PinPwm<TIMER_A, 4>,
+        2: PinPwm<TIMER_B, 4>,
+    }),
+    PB5: (pb5, 5, [0, 1, 2], {
+        1: PinPwm<TIMER_A, 5>,
+        2: PinPwm<TIMER_B, 5>,
+    }),
+    PB6: (pb6, 6, [0, 1, 2], {
+        1: PinPwm<TIMER_A, 6>,
+        2: PinPwm<TIMER_B, 6>,
+    }),
 ]);
 
 #[cfg(feature = "pac25140")]
 gpio!(GPIOC, gpioc, PC, 'C', PCn, [
-    PC4: (pc4, 4, [0, 1, 2, 3, 4, 5, 6, 7]),
-    PC5: (pc5, 5, [0, 1, 2, 3, 4, 5, 6, 7]),
+    PC4: (pc4, 4, [0, 1, 2, 3, 4, 5, 6, 7], {
+        1: PinPwm<TIMER_B, 4>,
+        2: PinPwm<TIMER_C, 4>,
+        4: PinMosi<SSPB>,
+        4: PinTx<USARTB>,
+        5: PinSck<SSPC>,
+        6: PinCanRx<CAN>,
+        7: PinScl<I2C>,
+    }),
+    PC5: (pc5, 5, [0, 1, 2, 3, 4, 5, 6, 7], {
+        1: PinPwm<TIMER_B, 5>,
+        2: PinPwm<TIMER_C, 5>,
+        4: PinMiso<SSPB>,
+        4: PinRx<USARTB>,
+        5: PinSs<SSPC>,
+        6: PinCanTx<CAN>,
+        7: PinSda<I2C>,
+    }),
 ]);
 
 #[cfg(feature = "pac25140")]
 gpio!(GPIOD, gpiod, PD, 'D', PDn, [
-    PD0: (pd0, 0, [0, 1, 2, 3, 5, 6, 7]),
-    PD1: (pd1, 1, [0, 1, 2, 3, 5, 6, 7]),
-    PD2: (pd2, 2, [0, 1, 2, 3, 5]),
-    PD3: (pd3, 3, [0, 1, 2, 5, 6, 7]),
-    PD4: (pd4, 4, [0, 1, 2, 3, 4, 5, 6, 7]),
-    PD5: (pd5, 5, [0, 1, 2, 3, 4, 5, 6, 7]),
+    PD0: (pd0, 0, [0, 1, 2, 3, 5, 6, 7], {
+        1: PinPwm<TIMER_B, 0>,
+        2: PinPwm<TIMER_C, 0>,
+        5: PinSck<SSPC>,
+        6: PinCanTx<CAN>,
+    }),
+    PD1: (pd1, 1, [0, 1, 2, 3, 5, 6, 7], {
+        1: PinPwm<TIMER_B, 1>,
+        2: PinPwm<TIMER_C, 1>,
+        5: PinSs<SSPC>,
+        6: PinCanRx<CAN>,
+    }),
+    PD2: (pd2, 2, [0, 1, 2, 3, 5], {
+        1: PinPwm<TIMER_B, 2>,
+        2: PinPwm<TIMER_C, 2>,
+        5: PinMosi<SSPC>,
+        5: PinTx<USARTC>,
+    }),
+    PD3: (pd3, 3, [0, 1, 2, 5, 6, 7], {
+        1: PinPwm<TIMER_B, 3>,
+        2: PinPwm<TIMER_C, 3>,
+        5: PinMiso<SSPC>,
+        5: PinRx<USARTC>,
+    }),
+    PD4: (pd4, 4, [0, 1, 2, 3, 4, 5, 6, 7], {
+        1: PinPwm<TIMER_B, 4>,
+        2: PinPwm<TIMER_C, 4>,
+        5: PinSck<SSPD>,
+        7: PinMosi<SSPD>,
+        7: PinTx<USARTD>,
+    }),
+    PD5: (pd5, 5, [0, 1, 2, 3, 4, 5, 6, 7], {
+        1: PinPwm<TIMER_B, 5>,
+        2: PinPwm<TIMER_C, 5>,
+        5: PinSck<SSPD>,
+        6: PinCanRx<CAN>,
+        7: PinMiso<SSPD>,
+        7: PinRx<USARTD>,
+    }),
 ]);
 
 #[cfg(feature = "pac25140")]
 gpio!(GPIOE, gpioe, PE, 'E', PEn, [
-    PE0: (pe0, 0, [0, 1, 2, 3, 4, 5, 6, 7]),
-    PE1: (pe1, 1, [0, 1, 2, 3, 4, 5, 6, 7]),
-    PE2: (pe2, 2, [0, 1, 2, 3, 4, 5, 6, 7]),
-    PE3: (pe3, 3, [0, 1, 2, 3, 5, 6]),
+    PE0: (pe0, 0, [0, 1, 2, 3, 4, 5, 6, 7], {
+        1: PinPwm<TIMER_C, 4>,
+        2: PinPwm<TIMER_D, 0>,
+        5: PinSck<SSPC>,
+        6: PinScl<I2C>,
+    }),
+    PE1: (pe1, 1, [0, 1, 2, 3, 4, 5, 6, 7], {
+        1: PinPwm<TIMER_C, 5>,
+        2: PinPwm<TIMER_D, 1>,
+        5: PinSs<SSPC>,
+        6: PinSda<I2C>,
+    }),
+    PE2: (pe2, 2, [0, 1, 2, 3, 4, 5, 6, 7], {
+        1: PinPwm<TIMER_C, 6>,
+        2: PinPwm<TIMER_D, 2>,
+        5: PinMosi<SSPC>,
+        5: PinTx<USARTC>,
+        6: PinCanRx<CAN>,
+    }),
+    PE3: (pe3, 3, [0, 1, 2, 3, 5, 6], {
+        1: PinPwm<TIMER_C, 7>,
+        2: PinPwm<TIMER_D, 3>,
+        5: PinMiso<SSPC>,
+        5: PinRx<USARTC>,
+        6: PinCanTx<CAN>,
+    }),
 ]);
 
 #[cfg(feature = "pac25140")]
 gpio!(GPIOF, gpiof, PF, 'F', PFn, [
-    PF0: (pf0, 0, [0, 1, 2, 3, 4, 5, 6, 7]),
-    PF1: (pf1, 1, [0, 1, 2, 3, 4, 5, 6, 7]),
-    PF2: (pf2, 2, [0, 1, 2, 3, 4, 5, 6, 7]),
-    PF3: (pf3, 3, [0, 1, 2, 3, 4, 5, 6, 7]),
-    PF4: (pf4, 4, [0, 1, 2, 4, 5, 6, 7]),
-    PF5: (pf5, 5, [0, 1, 2, 4, 5, 7]),
-    PF6: (pf6, 6, [0, 1, 2, 4, 5, 6, 7]),
-    PF7: (pf7, 7, [0, 1, 2, 5, 6, 7]),
+    PF0: (pf0, 0, [0, 1, 2, 3, 4, 5, 6, 7], super::Debugger, {
+        1: PinPwm<TIMER_C, 0>,
+        2: PinPwm<TIMER_D, 0>,
+        5: PinSck<SSPB>,
+    }),
+    PF1: (pf1, 1, [0, 1, 2, 3, 4, 5, 6, 7], super::Debugger, {
+        1: PinPwm<TIMER_C, 1>,
+        2: PinPwm<TIMER_D, 1>,
+        5: PinSs<SSPB>,
+    }),
+    PF2: (pf2, 2, [0, 1, 2, 3, 4, 5, 6, 7], {
+        1: PinPwm<TIMER_C, 2>,
+        2: PinPwm<TIMER_D, 2>,
+        5: PinMosi<SSPB>,
+        5: PinTx<USARTB>,
+    }),
+    PF3: (pf3, 3, [0, 1, 2, 3, 4, 5, 6, 7], {
+        1: PinPwm<TIMER_C, 3>,
+        2: PinPwm<TIMER_D, 3>,
+        5: PinMiso<SSPB>,
+        5: PinRx<USARTB>,
+    }),
+    PF4: (pf4, 4, [0, 1, 2, 4, 5, 6, 7], {
+        1: PinPwm<TIMER_C, 4>,
+        2: PinPwm<TIMER_D, 4>,
+        5: PinSck<SSPD>,
+    }),
+    PF5: (pf5, 5, [0, 1, 2, 4, 5, 7], {
+        1: PinPwm<TIMER_C, 5>,
+        2: PinPwm<TIMER_D, 5>,
+        5: PinSs<SSPD>,
+    }),
+    PF6: (pf6, 6, [0, 1, 2, 4, 5, 6, 7], {
+        1: PinPwm<TIMER_C, 6>,
+        2: PinPwm<TIMER_D, 6>,
+        5: PinMosi<SSPD>,
+        5: PinTx<USARTD>,
+        6: PinCanRx<CAN>,
+        7: PinScl<I2C>,
+    }),
+    PF7: (pf7, 7, [0, 1, 2, 5, 6, 7], {
+        1: PinPwm<TIMER_C, 7>,
+        2: PinPwm<TIMER_D, 7>,
+        5: PinMiso<SSPD>,
+        5: PinRx<USARTD>,
+        6: PinCanTx<CAN>,
+        7: PinSda<I2C>,
+    }),
 ]);
 
 #[cfg(feature = "pac25140")]
 gpio!(GPIOG, gpiog, PG, 'G', PGn, [
-    PG0: (pg0, 0, [0, 1, 2, 3, 5, 6, 7]),
-    PG1: (pg1, 1, [0, 1, 2, 3, 5, 6, 7]),
-    PG2: (pg2, 2, [0, 1, 2, 3, 5, 6, 7]),
-    PG3: (pg3, 3, [0, 1, 2, 5, 6]),
+    PG0: (pg0, 0, [0, 1, 2, 3, 5, 6, 7], {
+        1: PinPwm<TIMER_C, 0>,
+        2: PinPwm<TIMER_D, 0>,
+        5: PinSck<SSPD>,
+    }),
+    PG1: (pg1, 1, [0, 1, 2, 3, 5, 6, 7], {
+        1: PinPwm<TIMER_C, 1>,
+        2: PinPwm<TIMER_D, 1>,
+        5: PinSs<SSPD>,
+    }),
+    PG2: (pg2, 2, [0, 1, 2, 3, 5, 6, 7], {
+        1: PinPwm<TIMER_C, 2>,
+        2: PinPwm<TIMER_D, 2>,
+        5: PinMosi<SSPD>,
+        5: PinTx<USARTD>,
+    }),
+    PG3: (pg3, 3, [0, 1, 2, 5, 6], {
+        1: PinPwm<TIMER_C, 3>,
+        2: PinPwm<TIMER_D, 3>,
+        5: PinMiso<SSPD>,
+        5: PinRx<USARTD>,
+    }),
 ]);
+
+#[cfg(feature = "pac25140")]
+adc_channels!(
+    PA0: 0,
+    PA1: 1,
+    PA2: 2,
+    PA3: 3,
+    PA4: 4,
+    PA5: 5,
+    PA6: 6,
+    PA7: 7,
+);
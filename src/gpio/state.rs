@@ -0,0 +1,96 @@
+//! Snapshot of the hardware configuration of a GPIO port
+
+use super::*;
+
+/// Pad mode as configured in the GPIO MODE register
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PadMode {
+    /// Analog (reset state)
+    Analog,
+    /// Push pull output
+    PushPull,
+    /// Open drain output
+    OpenDrain,
+    /// Input
+    Input,
+}
+
+/// Hardware configuration of a single pin
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PadState {
+    /// Pad mode
+    pub mode: PadMode,
+    /// Alternate function selected in the SCC MUXSEL register, 0 is GPIO
+    pub alternate: u8,
+    /// Pull-up/pull-down setting
+    pub pull: Pull,
+    /// Drive strength
+    pub drive_strength: DriveStrength,
+    /// Level read from the pad
+    pub input: PinState,
+    /// Level held in the output data register
+    pub output: PinState,
+}
+
+/// Hardware configuration of all pins of a GPIO port
+///
+/// The snapshot is read straight from the GPIO and SCC registers, so it
+/// reflects the real pad state regardless of the type state of the pins.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PortState {
+    /// Port name, `'A'` for GPIOA
+    pub port: char,
+    /// Pin configuration indexed by pin number
+    pub pins: [PadState; 8],
+}
+
+impl PortState {
+    /// Reads the configuration of the port named `port`, `'A'` for GPIOA.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `port` does not name a GPIO port of the device.
+    pub fn read(port: char) -> Self {
+        assert!(('A'..='G').contains(&port), "Unknown GPIO port");
+        let index = port as u8 - b'A';
+        let block = port_block(index);
+
+        // NOTE(unsafe) atomic reads with no side effects
+        let (muxsel, ds, puen, pden) = unsafe {
+            let port = index as usize;
+            (
+                (*Gpio::<'A'>::scc_muxsel_ptr().add(port)).read().bits(),
+                (*Gpio::<'A'>::scc_ds_ptr().add(port)).read().bits(),
+                Gpio::<'A'>::scc_puen_ptr().add(port),
+                Gpio::<'A'>::scc_pden_ptr().add(port),
+            )
+        };
+        let mode = block.mode().read().bits();
+        let input = block.in_().read().bits();
+        let output = block.out().read().bits();
+
+        let pins = core::array::from_fn(|n| {
+            let n = n as u8;
+            let offset = 4 * n;
+
+            PadState {
+                mode: match (mode >> (2 * n)) & 0b11 {
+                    0b00 => PadMode::Analog,
+                    0b01 => PadMode::PushPull,
+                    0b10 => PadMode::OpenDrain,
+                    _ => PadMode::Input,
+                },
+                alternate: ((muxsel >> offset) & 0b111) as u8,
+                pull: unsafe { read_pull(puen, pden, n) },
+                drive_strength: DriveStrength::from_bits(ds >> offset),
+                input: PinState::from(input & (1 << n) != 0),
+                output: PinState::from(output & (1 << n) != 0),
+            }
+        });
+
+        Self { port, pins }
+    }
+}
@@ -0,0 +1,180 @@
+use super::*;
+use core::cell::RefCell;
+use core::future::Future;
+use core::pin::Pin as FuturePin;
+use core::task::{Context, Poll, Waker};
+use critical_section::Mutex;
+use embedded_hal_async::digital::Wait;
+
+const PORT_COUNT: usize = 7;
+const PIN_COUNT: usize = 8;
+
+/// Wakers of the tasks waiting on each pin, indexed by `port * 8 + pin`
+static WAKERS: [Mutex<RefCell<Option<Waker>>>; PORT_COUNT * PIN_COUNT] =
+    [const { Mutex::new(RefCell::new(None)) }; PORT_COUNT * PIN_COUNT];
+
+fn register_waker(port: u8, pin: u8, waker: &Waker) {
+    critical_section::with(|cs| {
+        let mut slot = WAKERS[port as usize * PIN_COUNT + pin as usize].borrow_ref_mut(cs);
+        match slot.as_ref() {
+            Some(registered) if registered.will_wake(waker) => {}
+            _ => *slot = Some(waker.clone()),
+        }
+    });
+}
+
+/// Wakes the tasks waiting on the pins of GPIO port `port`, `'A'` for GPIOA,
+/// whose interrupt fired.
+///
+/// The HAL does not define the `GPIOx` interrupt handlers, so the application
+/// has to call this function from the handler of every port it awaits pins
+/// on. Only pins with a waiting task are handled: their interrupt is disabled
+/// and its flag cleared. Other pending pins are left to the application.
+///
+/// ```ignore
+/// #[interrupt]
+/// fn GPIOD() {
+///     pac25_hal::gpio::on_interrupt('D');
+/// }
+/// ```
+pub fn on_interrupt(port: char) {
+    assert!(('A'..='G').contains(&port), "Unknown GPIO port");
+    let port = port as u8 - b'A';
+    let block = port_block(port);
+
+    // Disable the triggered pin interrupts so they do not fire again before
+    // the waiting task had the chance to run. The futures use the cleared
+    // enable bit to detect that their trigger happened.
+    critical_section::with(|cs| {
+        let pending = block.intflagmasked().read().bits() & 0xff;
+
+        for pin in 0..PIN_COUNT {
+            if pending & (1 << pin) == 0 {
+                continue;
+            }
+
+            let slot = &WAKERS[port as usize * PIN_COUNT + pin];
+            if let Some(waker) = slot.borrow_ref_mut(cs).take() {
+                unsafe {
+                    block.inten().modify(|r, w| w.bits(r.bits() & !(1 << pin)));
+                    block.intclear().write(|w| w.bits(1 << pin));
+                }
+                waker.wake();
+            }
+        }
+    });
+}
+
+/// Trigger a pin is waiting for
+#[derive(Clone, Copy)]
+enum Trigger {
+    Edge(Edge),
+    Level(PinState),
+}
+
+/// Future resolving once the pin interrupt fired
+///
+/// The pin interrupt is only enabled once the waker of the task is
+/// registered, so [`on_interrupt`] always finds the task to wake.
+struct InputFuture<'a, PIN: ExtiPin + PinExt> {
+    pin: &'a mut PIN,
+    trigger: Trigger,
+    armed: bool,
+}
+
+impl<'a, PIN: ExtiPin + PinExt> InputFuture<'a, PIN> {
+    fn new(pin: &'a mut PIN, trigger: Trigger) -> Self {
+        Self {
+            pin,
+            trigger,
+            armed: false,
+        }
+    }
+
+    fn arm(&mut self) {
+        let pin = &mut *self.pin;
+        let trigger = self.trigger;
+
+        critical_section::with(|_| {
+            match trigger {
+                Trigger::Edge(edge) => pin.trigger_on_edge(edge),
+                Trigger::Level(level) => pin.trigger_on_level(level),
+            }
+            pin.clear_interrupt_pending_bit();
+            pin.enable_interrupt();
+        });
+
+        unsafe {
+            crate::pac::NVIC::unmask(pin.interrupt());
+        }
+
+        self.armed = true;
+    }
+}
+
+impl<PIN: ExtiPin + PinExt> Future for InputFuture<'_, PIN> {
+    type Output = ();
+
+    fn poll(mut self: FuturePin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let port = self.pin.port_id();
+        let pin = self.pin.pin_id();
+
+        register_waker(port, pin, cx.waker());
+
+        if !self.armed {
+            self.arm();
+            Poll::Pending
+        } else if port_block(port).inten().read().bits() & (1 << pin) == 0 {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+impl<PIN: ExtiPin + PinExt> Drop for InputFuture<'_, PIN> {
+    fn drop(&mut self) {
+        let slot = self.pin.port_id() as usize * PIN_COUNT + self.pin.pin_id() as usize;
+
+        critical_section::with(|cs| {
+            if self.armed {
+                self.pin.disable_interrupt();
+            }
+            WAKERS[slot].borrow_ref_mut(cs).take();
+        });
+    }
+}
+
+impl<const P: char, const N: u8, MODE> Wait for Pin<P, N, MODE>
+where
+    MODE: marker::Readable + marker::Interruptible,
+{
+    async fn wait_for_high(&mut self) -> Result<(), Self::Error> {
+        if self.is_low() {
+            InputFuture::new(self, Trigger::Level(PinState::High)).await;
+        }
+        Ok(())
+    }
+
+    async fn wait_for_low(&mut self) -> Result<(), Self::Error> {
+        if self.is_high() {
+            InputFuture::new(self, Trigger::Level(PinState::Low)).await;
+        }
+        Ok(())
+    }
+
+    async fn wait_for_rising_edge(&mut self) -> Result<(), Self::Error> {
+        InputFuture::new(self, Trigger::Edge(Edge::Rising)).await;
+        Ok(())
+    }
+
+    async fn wait_for_falling_edge(&mut self) -> Result<(), Self::Error> {
+        InputFuture::new(self, Trigger::Edge(Edge::Falling)).await;
+        Ok(())
+    }
+
+    async fn wait_for_any_edge(&mut self) -> Result<(), Self::Error> {
+        InputFuture::new(self, Trigger::Edge(Edge::RisingFalling)).await;
+        Ok(())
+    }
+}
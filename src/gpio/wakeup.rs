@@ -0,0 +1,115 @@
+use super::exti::port_interrupt;
+use super::*;
+use crate::pac::NVIC;
+use cortex_m::peripheral::SCB;
+
+const PORT_COUNT: u8 = 7;
+
+/// Pin that woke the device up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct WakeupSource {
+    /// Port name, `'A'` for GPIOA
+    pub port: char,
+    /// Pin number
+    pub pin: u8,
+}
+
+impl WakeupSource {
+    /// Is `pin` the source of the wake-up?
+    pub fn is<PIN: PinExt>(&self, pin: &PIN) -> bool {
+        self.port as u8 - b'A' == pin.port_id() && self.pin == pin.pin_id()
+    }
+}
+
+/// Wake-up source configuration for GPIO pins
+///
+/// A wake-up pin uses the pin interrupt, so the edge configured here replaces
+/// any trigger set through [`ExtiPin`].
+pub trait WakeupPin {
+    /// Registers the pin as a wake-up source triggered by `edge`.
+    ///
+    /// The pin interrupt is enabled and the port interrupt unmasked in the
+    /// NVIC, so the pin wakes the core from sleep and deep sleep. Enter sleep
+    /// through [`wait_for_wakeup`], or provide a handler for the port
+    /// interrupt.
+    fn enable_wakeup(&mut self, edge: Edge);
+
+    /// Removes the pin from the wake-up sources and disables its interrupt.
+    ///
+    /// The port interrupt stays unmasked in the NVIC, as it is shared with the
+    /// other pins of the port.
+    fn disable_wakeup(&mut self);
+}
+
+impl<PIN> WakeupPin for PIN
+where
+    PIN: ExtiPin + PinExt,
+    PIN::Mode: marker::Interruptible,
+{
+    fn enable_wakeup(&mut self, edge: Edge) {
+        self.disable_interrupt();
+        self.trigger_on_edge(edge);
+        self.clear_interrupt_pending_bit();
+        self.enable_interrupt();
+
+        // NOTE(unsafe) the interrupt is only pended by enabled wake-up pins
+        unsafe { NVIC::unmask(self.interrupt()) };
+    }
+
+    fn disable_wakeup(&mut self) {
+        self.disable_interrupt();
+        self.clear_interrupt_pending_bit();
+    }
+}
+
+/// Puts the core to sleep until a wake-up pin triggers and reports that pin.
+///
+/// With `deep` set, the core enters deep sleep. Interrupts stay masked while
+/// the core sleeps, so the wake-up does not run the port interrupt handler:
+/// the interrupt flag of the waking pin is cleared and its pending port
+/// interrupt discarded. Returns `None` if the core was woken by anything other
+/// than a GPIO pin interrupt.
+pub fn wait_for_wakeup(scb: &mut SCB, deep: bool) -> Option<WakeupSource> {
+    // The pending interrupt has to wake the core without being taken, so
+    // PRIMASK is set around WFI, as a critical section implementation may
+    // not rely on it
+    let primask = cortex_m::register::primask::read();
+    cortex_m::interrupt::disable();
+
+    if deep {
+        scb.set_sleepdeep();
+    }
+    cortex_m::asm::dsb();
+    cortex_m::asm::wfi();
+    scb.clear_sleepdeep();
+
+    let source = critical_section::with(|_| {
+        (0..PORT_COUNT).find_map(|port| {
+            let block = port_block(port);
+            let pending = block.intflagmasked().read().bits() & 0xff;
+            if pending == 0 {
+                return None;
+            }
+
+            let pin = pending.trailing_zeros() as u8;
+            // NOTE(unsafe) atomic write to a stateless register
+            unsafe { block.intclear().write(|w| w.bits(1 << pin)) };
+            if block.intflagmasked().read().bits() & 0xff == 0 {
+                NVIC::unpend(port_interrupt(port));
+            }
+
+            Some(WakeupSource {
+                port: (b'A' + port) as char,
+                pin,
+            })
+        })
+    });
+
+    if primask.is_active() {
+        // NOTE(unsafe) interrupts were enabled on entry
+        unsafe { cortex_m::interrupt::enable() };
+    }
+
+    source
+}
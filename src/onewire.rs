@@ -0,0 +1,356 @@
+//! Dallas/Maxim 1-Wire bus master
+//!
+//! The bus is bit-banged on a pin in open drain output mode with an external
+//! pull-up resistor. Slot timing is derived from the core clock, and interrupts
+//! are masked during every time critical section of a slot.
+
+use crate::crc::Crc;
+use crate::gpio::{OpenDrain, Output, Pin};
+use crate::scc::Clocks;
+
+/// Read ROM command
+const READ_ROM: u8 = 0x33;
+/// Match ROM command
+const MATCH_ROM: u8 = 0x55;
+/// Skip ROM command
+const SKIP_ROM: u8 = 0xcc;
+/// Search ROM command
+const SEARCH_ROM: u8 = 0xf0;
+
+/// 1-Wire error
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Error {
+    /// No device answered the reset pulse with a presence pulse
+    NoPresence,
+    /// The bus is held low
+    BusShorted,
+    /// The received data failed the CRC check
+    CrcMismatch,
+    /// The received ROM code is all zeros, as read from a bus held low
+    InvalidRom,
+    /// No device answered a search slot
+    SearchFailed,
+}
+
+/// 64-bit device ROM code
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Rom(pub [u8; 8]);
+
+impl Rom {
+    /// Device family code
+    pub fn family_code(&self) -> u8 {
+        self.0[0]
+    }
+
+    /// 48-bit serial number
+    pub fn serial_number(&self) -> [u8; 6] {
+        let mut serial = [0; 6];
+        serial.copy_from_slice(&self.0[1..7]);
+        serial
+    }
+}
+
+/// State of a ROM search in progress
+///
+/// Pass the same state to successive [`OneWire::search_next`] calls to
+/// enumerate every device on the bus.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DeviceSearch {
+    rom: [u8; 8],
+    last_discrepancy: u8,
+    last_device: bool,
+}
+
+impl DeviceSearch {
+    /// Starts a new search.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// 1-Wire bus master
+pub struct OneWire<const P: char, const N: u8> {
+    pin: Pin<P, N, Output<OpenDrain>>,
+    cycles_per_us: u32,
+}
+
+impl<const P: char, const N: u8> OneWire<P, N> {
+    /// Creates a bus master on `pin` and releases the bus.
+    pub fn new(mut pin: Pin<P, N, Output<OpenDrain>>, clocks: &Clocks) -> Self {
+        pin.set_high();
+
+        Self {
+            pin,
+            cycles_per_us: clocks.hclk.raw().div_ceil(1_000_000),
+        }
+    }
+
+    /// Return ownership of the pin.
+    pub fn release(self) -> Pin<P, N, Output<OpenDrain>> {
+        self.pin
+    }
+
+    #[inline(always)]
+    fn delay_us(&self, us: u32) {
+        cortex_m::asm::delay(us * self.cycles_per_us);
+    }
+
+    /// Sends a reset pulse and waits for a presence pulse.
+    pub fn reset(&mut self) -> Result<(), Error> {
+        if self.pin.is_low() {
+            return Err(Error::BusShorted);
+        }
+
+        self.pin.set_low();
+        self.delay_us(480);
+
+        let present = cortex_m::interrupt::free(|_| {
+            self.pin.set_high();
+            self.delay_us(70);
+            self.pin.is_low()
+        });
+        self.delay_us(410);
+
+        if !present {
+            Err(Error::NoPresence)
+        } else if self.pin.is_low() {
+            Err(Error::BusShorted)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Writes a single bit.
+    pub fn write_bit(&mut self, bit: bool) {
+        let (low, high) = if bit { (6, 64) } else { (60, 10) };
+
+        cortex_m::interrupt::free(|_| {
+            self.pin.set_low();
+            self.delay_us(low);
+            self.pin.set_high();
+        });
+        self.delay_us(high);
+    }
+
+    /// Reads a single bit.
+    pub fn read_bit(&mut self) -> bool {
+        let bit = cortex_m::interrupt::free(|_| {
+            self.pin.set_low();
+            self.delay_us(6);
+            self.pin.set_high();
+            self.delay_us(9);
+            self.pin.is_high()
+        });
+        self.delay_us(55);
+
+        bit
+    }
+
+    /// Writes a byte, least significant bit first.
+    pub fn write_byte(&mut self, byte: u8) {
+        for i in 0..8 {
+            self.write_bit(byte & (1 << i) != 0);
+        }
+    }
+
+    /// Reads a byte, least significant bit first.
+    pub fn read_byte(&mut self) -> u8 {
+        (0..8).fold(0, |byte, i| byte | (u8::from(self.read_bit()) << i))
+    }
+
+    /// Writes all bytes of `data`.
+    pub fn write_bytes(&mut self, data: &[u8]) {
+        for &byte in data {
+            self.write_byte(byte);
+        }
+    }
+
+    /// Fills `data` with bytes read from the bus.
+    pub fn read_bytes(&mut self, data: &mut [u8]) {
+        for byte in data {
+            *byte = self.read_byte();
+        }
+    }
+
+    /// Resets the bus and addresses every device.
+    pub fn skip_rom(&mut self) -> Result<(), Error> {
+        self.reset()?;
+        self.write_byte(SKIP_ROM);
+        Ok(())
+    }
+
+    /// Resets the bus and addresses the device with the given ROM code.
+    pub fn match_rom(&mut self, rom: &Rom) -> Result<(), Error> {
+        self.reset()?;
+        self.write_byte(MATCH_ROM);
+        self.write_bytes(&rom.0);
+        Ok(())
+    }
+
+    /// Reads the ROM code of the only device on the bus.
+    pub fn read_rom(&mut self, crc: &Crc) -> Result<Rom, Error> {
+        self.reset()?;
+        self.write_byte(READ_ROM);
+
+        let mut rom = Rom::default();
+        self.read_bytes(&mut rom.0);
+        verify_rom(crc, &rom.0)?;
+
+        Ok(rom)
+    }
+
+    /// Finds the next device on the bus.
+    ///
+    /// Returns `Ok(None)` once every device has been reported.
+    pub fn search_next(
+        &mut self,
+        search: &mut DeviceSearch,
+        crc: &Crc,
+    ) -> Result<Option<Rom>, Error> {
+        if search.last_device {
+            return Ok(None);
+        }
+
+        self.reset()?;
+        self.write_byte(SEARCH_ROM);
+
+        let mut last_zero = 0;
+        for bit in 1..=64u8 {
+            let byte = usize::from((bit - 1) / 8);
+            let mask = 1 << ((bit - 1) % 8);
+
+            let id = self.read_bit();
+            let complement = self.read_bit();
+
+            let direction = match (id, complement) {
+                (true, true) => {
+                    *search = DeviceSearch::new();
+                    return Err(Error::SearchFailed);
+                }
+                (true, false) => true,
+                (false, true) => false,
+                (false, false) => {
+                    let direction = if bit < search.last_discrepancy {
+                        search.rom[byte] & mask != 0
+                    } else {
+                        bit == search.last_discrepancy
+                    };
+                    if !direction {
+                        last_zero = bit;
+                    }
+                    direction
+                }
+            };
+
+            if direction {
+                search.rom[byte] |= mask;
+            } else {
+                search.rom[byte] &= !mask;
+            }
+            self.write_bit(direction);
+        }
+
+        search.last_discrepancy = last_zero;
+        search.last_device = last_zero == 0;
+
+        if let Err(e) = verify_rom(crc, &search.rom) {
+            *search = DeviceSearch::new();
+            return Err(e);
+        }
+
+        Ok(Some(Rom(search.rom)))
+    }
+}
+
+/// Validates data whose last byte is the Dallas/Maxim CRC-8 of the rest.
+///
+/// A [`Crc`] must be configured with [`Polynomial::Crc8DallasMaxim`] and
+/// [`DataWidth::Width8Bits`]. Input and output reflection, which the
+/// Dallas/Maxim CRC-8 requires, are enabled by this function.
+///
+/// [`Polynomial::Crc8DallasMaxim`]: crate::crc::Polynomial::Crc8DallasMaxim
+/// [`DataWidth::Width8Bits`]: crate::crc::DataWidth::Width8Bits
+pub fn check_crc<C: Crc8>(crc: &C, data: &[u8]) -> Result<(), Error> {
+    if crc.crc8(data) == 0 {
+        Ok(())
+    } else {
+        Err(Error::CrcMismatch)
+    }
+}
+
+/// CRC-8 engine computing the Dallas/Maxim CRC-8
+pub trait Crc8: crate::Sealed {
+    #[doc(hidden)]
+    fn crc8(&self, data: &[u8]) -> u8;
+}
+
+impl crate::Sealed for Crc {}
+impl Crc8 for Crc {
+    fn crc8(&self, data: &[u8]) -> u8 {
+        self.reflect(true, true);
+        self.seed(0);
+        self.feed_u8(data);
+        self.result() as u8
+    }
+}
+
+/// Checks a ROM code received from the bus.
+///
+/// An all zero ROM code passes the CRC check, but is what a bus held low
+/// reads as.
+fn verify_rom<C: Crc8>(crc: &C, rom: &[u8; 8]) -> Result<(), Error> {
+    if rom.iter().all(|&byte| byte == 0) {
+        return Err(Error::InvalidRom);
+    }
+    check_crc(crc, rom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Bitwise model of the reflected Dallas/Maxim CRC-8 engine
+    struct SoftCrc8;
+
+    impl crate::Sealed for SoftCrc8 {}
+    impl Crc8 for SoftCrc8 {
+        fn crc8(&self, data: &[u8]) -> u8 {
+            data.iter().fold(0, |crc, &byte| {
+                (0..8).fold(crc ^ byte, |crc, _| {
+                    if crc & 1 != 0 {
+                        (crc >> 1) ^ 0x8c
+                    } else {
+                        crc >> 1
+                    }
+                })
+            })
+        }
+    }
+
+    #[test]
+    fn application_note_rom() {
+        // Example from Maxim application note 27: family 0x02, serial
+        // 0x00000001B81C, CRC 0xA2
+        let rom = [0x02, 0x1c, 0xb8, 0x01, 0x00, 0x00, 0x00, 0xa2];
+        assert_eq!(SoftCrc8.crc8(&rom[..7]), 0xa2);
+        assert_eq!(verify_rom(&SoftCrc8, &rom), Ok(()));
+    }
+
+    #[test]
+    fn ds18b20_rom() {
+        let rom = [0x28, 0xff, 0x4c, 0x5f, 0x61, 0x16, 0x04, 0xeb];
+        assert_eq!(verify_rom(&SoftCrc8, &rom), Ok(()));
+        assert_eq!(Rom(rom).family_code(), 0x28);
+
+        let mut corrupted = rom;
+        corrupted[3] ^= 0x10;
+        assert_eq!(verify_rom(&SoftCrc8, &corrupted), Err(Error::CrcMismatch));
+    }
+
+    #[test]
+    fn all_zero_rom_is_rejected() {
+        assert_eq!(verify_rom(&SoftCrc8, &[0; 8]), Err(Error::InvalidRom));
+    }
+}
@@ -1,3 +1,5 @@
+pub use crate::gpio::ExtiPin as _pac25xxx_hal_gpio_ExtiPin;
 pub use crate::gpio::GpioExt as _pac25xxx_hal_gpio_GpioExt;
+pub use crate::gpio::WakeupPin as _pac25xxx_hal_gpio_WakeupPin;
 pub use crate::scc::SccExt as _pac25xxx_hal_scc_SccExt;
 pub use crate::ssp::SspExt as _pac25xxx_hal_ssp_SspExt;
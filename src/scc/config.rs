@@ -15,6 +15,17 @@ pub enum ClockSource {
     External(Hertz),
 }
 
+impl ClockSource {
+    /// Frequency of the clock source.
+    pub(crate) fn freq(&self) -> Hertz {
+        match self {
+            ClockSource::RingOsc => Hertz::MHz(16),
+            ClockSource::RefClock => Hertz::MHz(4),
+            ClockSource::External(freq) => *freq,
+        }
+    }
+}
+
 /// System clock selection.
 #[derive(Debug, Clone, Copy)]
 pub enum SystemClock {
@@ -42,6 +53,7 @@ pub struct Config {
     pub(crate) sclk_mux: SystemClock,
     pub(crate) rosc_enable: bool,
     pub(crate) frclk_mux: ClockSource,
+    pub(crate) flash_cache: bool,
     pub pll: PllConfig,
 }
 
@@ -56,6 +68,7 @@ impl Default for Config {
             sclk_mux: SystemClock::FRCLK,
             rosc_enable: true,
             frclk_mux: ClockSource::RingOsc,
+            flash_cache: true,
             pll: PllConfig::default(),
         }
     }
@@ -99,6 +112,8 @@ impl Config {
     }
 
     /// Enable or disable the ROSC.
+    ///
+    /// The ROSC can only be disabled if it is not the FRCLK source.
     pub fn rosc_enable(mut self, enabled: bool) -> Self {
         self.rosc_enable = enabled;
         self
@@ -110,6 +125,15 @@ impl Config {
         self
     }
 
+    /// Enable or disable the FLASH read cache.
+    ///
+    /// The PAC25140 has no separate FLASH prefetch control, sequential reads
+    /// are served by the read cache.
+    pub fn flash_cache(mut self, enabled: bool) -> Self {
+        self.flash_cache = enabled;
+        self
+    }
+
     /// Set the PLL configuration.
     pub fn pll(mut self, config: PllConfig) -> Self {
         self.pll = config;
@@ -121,7 +145,7 @@ impl Config {
 pub type PllOutPrescaler = PLLOUTDIV;
 
 /// PLL configuration.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub struct PllConfig {
     pub(crate) fbdiv: u16,
     pub(crate) indiv: u8,
@@ -143,22 +167,29 @@ impl Default for PllConfig {
 }
 
 impl PllConfig {
+    /// Lowest phase frequency detector input, after the input divider.
+    pub const PFD_MIN: Hertz = Hertz::MHz(1);
+    /// Highest phase frequency detector input, after the input divider.
+    pub const PFD_MAX: Hertz = Hertz::MHz(15);
+    /// Lowest VCO frequency.
+    pub const VCO_MIN: Hertz = Hertz::MHz(200);
+    /// Highest VCO frequency.
+    pub const VCO_MAX: Hertz = Hertz::MHz(600);
+
     /// Set the feedback divider.
     ///
-    /// Must be between 4 and 16383.
+    /// Must be between 4 and 16383, which is checked when the configuration
+    /// is applied.
     pub fn fbdiv(mut self, divider: u16) -> Self {
-        assert!(divider >= 4);
-        assert!(divider <= 16383);
         self.fbdiv = divider;
         self
     }
 
     /// Set the input divider.
     ///
-    /// Must be between 1 and 15.
+    /// Must be between 1 and 15, which is checked when the configuration is
+    /// applied.
     pub fn indiv(mut self, divider: u8) -> Self {
-        assert!(divider >= 1);
-        assert!(divider <= 15);
         self.indiv = divider;
         self
     }
@@ -183,17 +214,175 @@ impl PllConfig {
 
     /// Calculator the voltage controlled oscillator output frequency.
     pub fn vco_freq(&self, input: Hertz) -> Hertz {
-        let fbdiv = self.fbdiv as f32;
-        let indiv = self.indiv as f32;
-        let vco = input.to_Hz() as f32 * (fbdiv / indiv);
+        let vco = (input.to_Hz() as u64 * self.fbdiv as u64)
+            .checked_div(self.indiv as u64)
+            .unwrap_or(0);
         Hertz::Hz(vco as u32)
     }
 
     /// Calculate the PLL output frequency.
     pub fn out_freq(&self, input: Hertz) -> Hertz {
-        let outdiv = self.outdiv as u32 + 1;
-        let vco = self.vco_freq(input).to_Hz() as f32;
-        let out = vco / outdiv as f32;
+        let outdiv = self.outdiv as u64 + 1;
+        let out = (input.to_Hz() as u64 * self.fbdiv as u64)
+            .checked_div(self.indiv as u64 * outdiv)
+            .unwrap_or(0);
         Hertz::Hz(out as u32)
     }
+
+    /// Find the enabled PLL configuration whose output frequency is closest to
+    /// `target` for the given input frequency.
+    ///
+    /// Only dividers keeping the phase frequency detector input between
+    /// [`PFD_MIN`](Self::PFD_MIN) and [`PFD_MAX`](Self::PFD_MAX) and the VCO
+    /// between [`VCO_MIN`](Self::VCO_MIN) and [`VCO_MAX`](Self::VCO_MAX) are
+    /// considered. Among equally close configurations, the one with the
+    /// smallest input divider is chosen.
+    ///
+    /// Returns the configuration and the output frequency it achieves, or
+    /// `None` if no divider setting is legal for `input`.
+    pub fn for_target(input: Hertz, target: Hertz) -> Option<(Self, Hertz)> {
+        let input = input.to_Hz() as u64;
+        let target = target.to_Hz() as u64;
+
+        // Error of the best configuration, as the fraction `num / den` Hz
+        let mut best: Option<(Self, u64, u64)> = None;
+
+        for indiv in 1..=15u64 {
+            let pfd = input / indiv;
+            if input < Self::PFD_MIN.to_Hz() as u64 * indiv || pfd > Self::PFD_MAX.to_Hz() as u64 {
+                continue;
+            }
+
+            for (outdiv, prescaler) in [
+                PllOutPrescaler::Div1,
+                PllOutPrescaler::Div2,
+                PllOutPrescaler::Div3,
+                PllOutPrescaler::Div4,
+            ]
+            .into_iter()
+            .enumerate()
+            {
+                let den = indiv * (outdiv as u64 + 1);
+
+                // The feedback dividers around the ideal one, limited to the
+                // VCO range
+                let fb_min = (Self::VCO_MIN.to_Hz() as u64 * indiv).div_ceil(input);
+                let fb_max = Self::VCO_MAX.to_Hz() as u64 * indiv / input;
+                let fb_min = fb_min.max(4);
+                let fb_max = fb_max.min(16383);
+                if fb_min > fb_max {
+                    continue;
+                }
+
+                let ideal = target * den / input;
+                for fbdiv in [ideal, ideal + 1] {
+                    let fbdiv = fbdiv.clamp(fb_min, fb_max);
+                    let num = (input * fbdiv).abs_diff(target * den);
+
+                    let better = match best {
+                        // num / den < best_num / best_den
+                        Some((_, best_num, best_den)) => {
+                            (num as u128) * (best_den as u128) < (best_num as u128) * (den as u128)
+                        }
+                        None => true,
+                    };
+
+                    if better {
+                        let config = Self {
+                            fbdiv: fbdiv as u16,
+                            indiv: indiv as u8,
+                            outdiv: prescaler,
+                            bypass: false,
+                            enabled: true,
+                        };
+                        best = Some((config, num, den));
+                    }
+                }
+            }
+        }
+
+        best.map(|(config, _, _)| {
+            let freq = config.out_freq(Hertz::Hz(input as u32));
+            (config, freq)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fugit::RateExtU32;
+
+    fn check_limits(config: &PllConfig, input: Hertz) {
+        let pfd = input.to_Hz() / config.indiv as u32;
+        assert!(pfd >= PllConfig::PFD_MIN.to_Hz() && pfd <= PllConfig::PFD_MAX.to_Hz());
+
+        let vco = config.vco_freq(input);
+        assert!(vco >= PllConfig::VCO_MIN && vco <= PllConfig::VCO_MAX);
+
+        assert!((4..=16383).contains(&config.fbdiv));
+        assert!(config.enabled);
+        assert!(!config.bypass);
+    }
+
+    #[test]
+    fn exact_targets() {
+        let cases = [
+            (4.MHz(), 150.MHz()),
+            (4.MHz(), 100.MHz()),
+            (4.MHz(), 60.MHz()),
+            (4.MHz(), 75.MHz()),
+            (16.MHz(), 150.MHz()),
+            (16.MHz(), 120.MHz()),
+            (16.MHz(), 50.MHz()),
+            (12.MHz(), 96.MHz()),
+        ];
+
+        for (input, target) in cases {
+            let (config, freq) = PllConfig::for_target(input, target).unwrap();
+            assert_eq!(freq, target, "{input} -> {target}");
+            assert_eq!(config.out_freq(input), freq);
+            check_limits(&config, input);
+        }
+    }
+
+    #[test]
+    fn closest_target() {
+        // With a 4 MHz input the finest output step around 100 MHz is
+        // 250 kHz, so 100 MHz is the closest to 100.1 MHz
+        let input = 4.MHz();
+        let (config, freq) = PllConfig::for_target(input, 100_100.kHz()).unwrap();
+        check_limits(&config, input);
+        assert_eq!(freq, config.out_freq(input));
+        assert_eq!(freq, Hertz::MHz(100));
+    }
+
+    #[test]
+    fn exact_frequency_is_not_rounded() {
+        // 4 MHz * 301 / 3 / 4 = 100.333... MHz
+        let config = PllConfig::default()
+            .indiv(3)
+            .fbdiv(301)
+            .outdiv(PllOutPrescaler::Div4);
+        assert_eq!(config.vco_freq(4.MHz()), Hertz::Hz(401_333_333));
+        assert_eq!(config.out_freq(4.MHz()), Hertz::Hz(100_333_333));
+    }
+
+    #[test]
+    fn out_of_range_targets_saturate() {
+        let input = 4.MHz();
+
+        let (config, freq) = PllConfig::for_target(input, 2_000.MHz()).unwrap();
+        check_limits(&config, input);
+        assert_eq!(freq, PllConfig::VCO_MAX);
+
+        let (config, freq) = PllConfig::for_target(input, 1.MHz()).unwrap();
+        check_limits(&config, input);
+        assert_eq!(freq, PllConfig::VCO_MIN / 4);
+    }
+
+    #[test]
+    fn input_below_pfd_range() {
+        assert!(PllConfig::for_target(500.kHz(), 100.MHz()).is_none());
+    }
 }
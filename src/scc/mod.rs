@@ -3,8 +3,63 @@ pub mod config;
 pub use config::*;
 
 use crate::time::Hertz;
-use fugit::RateExtU32;
-use pac25::pac25140::SCC;
+use cortex_m::peripheral::SYST;
+use pac25::pac25140::{scc::ccsctl, MEMCTL, SCC};
+
+/// Highest FLASH clock frequency per access cycle.
+const FLASH_MAX_FREQ: Hertz = Hertz::MHz(25);
+/// PLL lock timeout in periods of the 4MHz reference clock (2 ms).
+const PLL_LOCK_TIMEOUT: u32 = 8_000;
+/// SysTick enable, with the external reference as clock source.
+const SYST_CSR_ENABLE: u32 = 1 << 0;
+/// SysTick counter reached zero since the last read.
+const SYST_CSR_COUNTFLAG: u32 = 1 << 16;
+/// Highest HCLK frequency.
+pub const HCLK_MAX: Hertz = Hertz::MHz(150);
+/// Highest ACLK frequency.
+pub const ACLK_MAX: Hertz = Hertz::MHz(300);
+/// Highest PCLK frequency.
+pub const PCLK_MAX: Hertz = Hertz::MHz(150);
+
+/// Clock configuration error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ClockError {
+    /// The ring oscillator is disabled while it feeds FRCLK.
+    RingOscDisabled,
+    /// The external clock frequency is zero.
+    ExternalClockMissing,
+    /// PLLCLK is selected as SCLK while the PLL is disabled.
+    PllDisabled,
+    /// The PLL input divider is outside of 1 to 15.
+    InputDivider(u8),
+    /// The PLL feedback divider is outside of 4 to 16383.
+    FeedbackDivider(u16),
+    /// The PLL phase frequency detector input is out of range.
+    PfdFrequency(Hertz),
+    /// The PLL VCO frequency is out of range.
+    VcoFrequency(Hertz),
+    /// HCLK exceeds [`HCLK_MAX`].
+    HclkTooHigh(Hertz),
+    /// ACLK exceeds [`ACLK_MAX`].
+    AclkTooHigh(Hertz),
+    /// PCLK exceeds [`PCLK_MAX`].
+    PclkTooHigh(Hertz),
+    /// Switching SCLK to the PLL failed at `stage`. The PLL was disabled and
+    /// SCLK left on FRCLK, with the rest of the clock tree configured as
+    /// requested.
+    PllFallback(ClockStage),
+}
+
+/// Stage of the switch from FRCLK to PLLCLK.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ClockStage {
+    /// The PLL did not lock within 2 ms of the reference clock.
+    PllLock,
+    /// The PLL lost its lock right after SCLK was switched to it.
+    SclkSwitch,
+}
 
 /// Clock frequencies.
 #[derive(Debug, Clone, Copy)]
@@ -14,10 +69,12 @@ pub struct Clocks {
     pub sclk: Hertz,
     /// AHB clock frequency.
     pub hclk: Hertz,
-    /// Auxiliary clock frequency.
-    pub aclk: Hertz,
-    /// Peripheral clock frequency.
-    pub pclk: Hertz,
+    /// Auxiliary clock frequency, `None` if the domain is disabled.
+    pub aclk: Option<Hertz>,
+    /// Peripheral clock frequency, `None` if the domain is disabled.
+    pub pclk: Option<Hertz>,
+    /// FLASH access wait states.
+    pub flash_wait_states: u8,
 }
 
 impl Default for Clocks {
@@ -25,8 +82,9 @@ impl Default for Clocks {
         Self {
             sclk: Hertz::Hz(0),
             hclk: Hertz::Hz(0),
-            aclk: Hertz::Hz(0),
-            pclk: Hertz::Hz(0),
+            aclk: None,
+            pclk: None,
+            flash_wait_states: 0,
         }
     }
 }
@@ -38,31 +96,111 @@ pub struct Scc {
 }
 
 impl Scc {
+    /// Read-modify-write of the CCSCTL register.
+    ///
+    /// CCSCTL holds the clock tree configuration as well as the mode of
+    /// shared peripherals, so the update runs in a critical section.
+    pub(crate) fn modify_ccsctl<F>(&mut self, f: F)
+    where
+        for<'w> F: FnOnce(&ccsctl::R, &'w mut ccsctl::W) -> &'w mut ccsctl::W,
+    {
+        critical_section::with(|_| {
+            self.reg.ccsctl().modify(f);
+        });
+    }
+
     /// Apply clock configuration.
+    ///
+    /// If switching to the PLL fails, SCLK falls back to FRCLK and every
+    /// frequency in [`Scc::clocks`] is derived from FRCLK instead, so
+    /// `clocks.sclk` tells whether the PLL is in use. Use
+    /// [`Scc::try_freeze`] to get the failed stage instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the configuration is invalid, see [`Scc::try_freeze`].
     pub fn freeze(mut self, config: Config) -> Self {
+        let sclk = match validate(&config) {
+            Ok(sclk) => sclk,
+            Err(e) => panic!("invalid clock configuration: {:?}", e),
+        };
+
+        // The fallback leaves a working clock tree, and the clocks are
+        // derived from the SCLK actually in use
+        if let Err(e) = self.apply(config, sclk) {
+            debug_assert!(matches!(e, ClockError::PllFallback(_)));
+        }
+        self
+    }
+
+    /// Apply clock configuration, if it is valid.
+    ///
+    /// The configuration is checked before any register is written. The FLASH
+    /// wait states are raised before the clocks are sped up and
+    /// lowered once they settled, and the FLASH controller is clocked from
+    /// HCLK.
+    ///
+    /// The PLL lock wait is bounded to 2 ms, counted by SysTick from its
+    /// external reference, independent of the FRCLK frequency the
+    /// configuration assumes. SysTick is taken over for the wait and its
+    /// current count reset to 0, and its external reference is assumed to be
+    /// the 4MHz CLKREF. If the PLL does not lock, or loses its lock
+    /// once SCLK is switched to it, the PLL is disabled, SCLK stays on FRCLK
+    /// and [`ClockError::PllFallback`] names the failed stage.
+    ///
+    /// The `Scc` is returned along with the error. After an invalid
+    /// configuration the clock tree is left untouched, after a PLL fallback
+    /// [`Scc::clocks`] holds the frequencies derived from FRCLK.
+    pub fn try_freeze(mut self, config: Config) -> Result<Self, (Self, ClockError)> {
+        let sclk = match validate(&config) {
+            Ok(sclk) => sclk,
+            Err(e) => return Err((self, e)),
+        };
+
+        match self.apply(config, sclk) {
+            Ok(()) => Ok(self),
+            Err(e) => Err((self, e)),
+        }
+    }
+
+    /// Programs a validated clock configuration with the expected SCLK
+    /// frequency.
+    fn apply(&mut self, config: Config, sclk: Hertz) -> Result<(), ClockError> {
+        let frclk = config.frclk_mux.freq();
+
+        // HCLK never exceeds SCLK, whatever divider is active while
+        // switching, so wait states for SCLK are safe until HCLK is set
+        let memctl = unsafe { &*MEMCTL::ptr() };
+        let current = memctl.memctl().read().wstate().bits();
+        let wait_states = flash_wait_states(sclk).max(current);
+        memctl
+            .memctl()
+            .modify(|_, w| unsafe { w.wstate().bits(wait_states) });
+
+        // Start the ROSC before it can be selected
+        if config.rosc_enable {
+            self.modify_ccsctl(|_, w| w.roscen().set_bit());
+        }
+
+        // Never run from the PLL while its input or dividers change
+        self.modify_ccsctl(|_, w| w.sclkmuxsel().frclk());
+
         // Configure FRCLK
-        let frclk = match config.frclk_mux {
+        match config.frclk_mux {
             ClockSource::RingOsc => {
-                self.reg.ccsctl().modify(|_, w| w.roscen().set_bit());
-                self.reg.ccsctl().modify(|_, w| w.frclkmuxsel().rosc());
-                16_u32.MHz()
+                self.modify_ccsctl(|_, w| w.frclkmuxsel().rosc());
             }
             ClockSource::RefClock => {
-                self.reg.ccsctl().modify(|_, w| w.frclkmuxsel().clkref());
-                4_u32.MHz()
+                self.modify_ccsctl(|_, w| w.frclkmuxsel().clkref());
             }
-            ClockSource::External(freq) => {
-                self.reg.ccsctl().modify(|_, w| w.frclkmuxsel().extclk());
-                freq
+            ClockSource::External(_) => {
+                self.modify_ccsctl(|_, w| w.frclkmuxsel().extclk());
             }
         };
 
         // Configure SCLK
-        let sclk = match config.sclk_mux {
-            SystemClock::FRCLK => {
-                self.reg.ccsctl().modify(|_, w| w.sclkmuxsel().frclk());
-                frclk
-            }
+        let (sclk, result) = match config.sclk_mux {
+            SystemClock::FRCLK => (sclk, Ok(())),
             SystemClock::PLLCLK => {
                 self.reg.ccspllctl().modify(|_, w| unsafe {
                     w.plloutdiv()
@@ -77,44 +215,188 @@ impl Scc {
                         .bit(config.pll.enabled)
                 });
 
-                while self.reg.ccspllctl().read().plllock().bit_is_clear() {}
-
-                self.reg.ccsctl().modify(|_, w| w.sclkmuxsel().pllclk());
-
-                config.pll.out_freq(frclk)
+                match self.switch_to_pll() {
+                    Ok(()) => (sclk, Ok(())),
+                    Err(stage) => {
+                        self.modify_ccsctl(|_, w| w.sclkmuxsel().frclk());
+                        self.reg.ccspllctl().modify(|_, w| w.pllen().clear_bit());
+                        (frclk, Err(ClockError::PllFallback(stage)))
+                    }
+                }
             }
         };
 
         // Configure HCLK
         let hdiv = config.hclk as u32 + 1;
         let hclk = Hertz::Hz(sclk.to_Hz() / hdiv);
-        self.reg
-            .ccsctl()
-            .modify(|_, w| w.hclkdiv().variant(config.hclk));
+        self.modify_ccsctl(|_, w| w.hclkdiv().variant(config.hclk));
+
+        // Clock the FLASH from HCLK with the minimum wait states for it
+        let wait_states = flash_wait_states(hclk);
+        memctl.memctl().modify(|_, w| unsafe {
+            w.mclkdiv()
+                .bits(0)
+                .mclksel()
+                .set_bit()
+                .cachedis()
+                .bit(!config.flash_cache)
+        });
+        memctl
+            .memctl()
+            .modify(|_, w| unsafe { w.wstate().bits(wait_states) });
+
+        // Stop the ROSC once nothing is clocked from it anymore
+        if !config.rosc_enable {
+            self.modify_ccsctl(|_, w| w.roscen().clear_bit());
+        }
 
         // Configure ACLK
         let adiv = config.aclk as u32 + 1;
-        let aclk = Hertz::Hz(sclk.to_Hz() / adiv);
-        self.reg
-            .ccsctl()
-            .modify(|_, w| w.aclkdiv().variant(config.aclk));
+        let aclk = config.aclk_enable.then(|| Hertz::Hz(sclk.to_Hz() / adiv));
+        self.modify_ccsctl(|_, w| {
+            w.aclkdiv()
+                .variant(config.aclk)
+                .aclken()
+                .bit(config.aclk_enable)
+        });
 
         // Configure PCLK
         let pdiv = config.pclk as u32 + 1;
-        let pclk = Hertz::Hz(hclk.to_Hz() / pdiv);
-        self.reg
-            .ccsctl()
-            .modify(|_, w| w.pclkdiv().variant(config.pclk));
+        let pclk = config.pclk_enbable.then(|| Hertz::Hz(hclk.to_Hz() / pdiv));
+        self.modify_ccsctl(|_, w| {
+            w.pclkdiv()
+                .variant(config.pclk)
+                .pclken()
+                .bit(config.pclk_enbable)
+        });
 
         self.clocks = Clocks {
             sclk,
             hclk,
             aclk,
             pclk,
+            flash_wait_states: wait_states,
         };
 
-        self
+        result
     }
+
+    /// Switches SCLK to the enabled PLL once it locked.
+    fn switch_to_pll(&mut self) -> Result<(), ClockStage> {
+        if !self.wait_for_pll_lock() {
+            return Err(ClockStage::PllLock);
+        }
+
+        self.modify_ccsctl(|_, w| w.sclkmuxsel().pllclk());
+        if !self.reg.ccspllctl().read().plllock().bit_is_set() {
+            return Err(ClockStage::SclkSwitch);
+        }
+
+        Ok(())
+    }
+
+    /// Waits for the PLL to lock, for at most [`PLL_LOCK_TIMEOUT`] reference
+    /// clock periods.
+    ///
+    /// SysTick is taken over for the wait, with its external clock source
+    /// selected and its interrupt disabled. Its control and reload values are
+    /// restored afterwards, but the current count (CVR) is reset to 0.
+    ///
+    /// The bound assumes the external SysTick reference is the 4MHz CLKREF,
+    /// which the PAC does not confirm.
+    fn wait_for_pll_lock(&self) -> bool {
+        // SAFETY: SysTick is only borrowed in the middle of freezing the
+        // clocks, its state is restored before returning
+        let syst = unsafe { &*SYST::PTR };
+        let csr = syst.csr.read();
+        let rvr = syst.rvr.read();
+
+        unsafe {
+            syst.csr.write(0);
+            syst.rvr.write(PLL_LOCK_TIMEOUT - 1);
+            syst.cvr.write(0);
+            syst.csr.write(SYST_CSR_ENABLE);
+        }
+
+        let locked = loop {
+            if self.reg.ccspllctl().read().plllock().bit_is_set() {
+                break true;
+            }
+            if syst.csr.read() & SYST_CSR_COUNTFLAG != 0 {
+                break self.reg.ccspllctl().read().plllock().bit_is_set();
+            }
+        };
+
+        unsafe {
+            syst.csr.write(0);
+            syst.rvr.write(rvr);
+            syst.cvr.write(0);
+            syst.csr.write(csr & !SYST_CSR_COUNTFLAG);
+        }
+
+        locked
+    }
+}
+
+/// Checks a clock configuration and returns the resulting SCLK frequency.
+fn validate(config: &Config) -> Result<Hertz, ClockError> {
+    let frclk = match config.frclk_mux {
+        ClockSource::RingOsc if !config.rosc_enable => return Err(ClockError::RingOscDisabled),
+        ClockSource::External(freq) if freq.to_Hz() == 0 => {
+            return Err(ClockError::ExternalClockMissing)
+        }
+        source => source.freq(),
+    };
+
+    let sclk = match config.sclk_mux {
+        SystemClock::FRCLK => frclk,
+        SystemClock::PLLCLK => {
+            let pll = &config.pll;
+            if !pll.enabled {
+                return Err(ClockError::PllDisabled);
+            }
+            if !(1..=15).contains(&pll.indiv) {
+                return Err(ClockError::InputDivider(pll.indiv));
+            }
+            if !(4..=16383).contains(&pll.fbdiv) {
+                return Err(ClockError::FeedbackDivider(pll.fbdiv));
+            }
+
+            let pfd = Hertz::Hz(frclk.to_Hz() / pll.indiv as u32);
+            if pfd < PllConfig::PFD_MIN || pfd > PllConfig::PFD_MAX {
+                return Err(ClockError::PfdFrequency(pfd));
+            }
+            let vco = pll.vco_freq(frclk);
+            if vco < PllConfig::VCO_MIN || vco > PllConfig::VCO_MAX {
+                return Err(ClockError::VcoFrequency(vco));
+            }
+
+            pll.out_freq(frclk)
+        }
+    };
+
+    let hclk = Hertz::Hz(sclk.to_Hz() / (config.hclk as u32 + 1));
+    if hclk > HCLK_MAX {
+        return Err(ClockError::HclkTooHigh(hclk));
+    }
+
+    let aclk = Hertz::Hz(sclk.to_Hz() / (config.aclk as u32 + 1));
+    if config.aclk_enable && aclk > ACLK_MAX {
+        return Err(ClockError::AclkTooHigh(aclk));
+    }
+
+    let pclk = Hertz::Hz(hclk.to_Hz() / (config.pclk as u32 + 1));
+    if config.pclk_enbable && pclk > PCLK_MAX {
+        return Err(ClockError::PclkTooHigh(pclk));
+    }
+
+    Ok(sclk)
+}
+
+/// Minimum FLASH wait states for the FLASH controller clocked at `mclk`.
+fn flash_wait_states(mclk: Hertz) -> u8 {
+    let cycles = mclk.to_Hz().div_ceil(FLASH_MAX_FREQ.to_Hz());
+    cycles.saturating_sub(1).min(15) as u8
 }
 
 pub trait SccExt {
@@ -123,6 +405,10 @@ pub trait SccExt {
 
     /// Constains the `SCC` peripheral and applies a clock configuration.
     fn freeze(self, config: Config) -> Scc;
+
+    /// Constains the `SCC` peripheral and applies a clock configuration, if
+    /// it is valid.
+    fn try_freeze(self, config: Config) -> Result<Scc, (Scc, ClockError)>;
 }
 
 impl SccExt for SCC {
@@ -136,4 +422,23 @@ impl SccExt for SCC {
     fn freeze(self, config: Config) -> Scc {
         self.constain().freeze(config)
     }
+
+    fn try_freeze(self, config: Config) -> Result<Scc, (Scc, ClockError)> {
+        self.constain().try_freeze(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fugit::RateExtU32;
+
+    #[test]
+    fn flash_wait_states_are_minimal() {
+        assert_eq!(flash_wait_states(0.MHz()), 0);
+        assert_eq!(flash_wait_states(25.MHz()), 0);
+        assert_eq!(flash_wait_states(26.MHz()), 1);
+        assert_eq!(flash_wait_states(150.MHz()), 5);
+        assert_eq!(flash_wait_states(151.MHz()), 6);
+    }
 }
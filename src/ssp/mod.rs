@@ -14,7 +14,7 @@ impl SspExt for SSPA {
     /// Creates an SPI interface.
     fn spi(self, scc: &mut Scc, config: Config) -> Spi {
         // configure peripheral into ssp mode
-        scc.reg.ccsctl().modify(|_, w| w.usamode().ssp());
+        scc.modify_ccsctl(|_, w| w.usamode().ssp());
 
         // configure clock
         self.clk()